@@ -0,0 +1,183 @@
+use crate::Solution;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The accepted answers for a day, as stored in `answers/dayXX`:
+///
+/// ```text
+/// part1: 54561
+/// part2: 54076
+/// ```
+///
+/// Either line can be left out if that part has not been accepted yet. A multi-line answer, like
+/// a picture of block letters, goes on the indented lines after its `partN:`:
+///
+/// ```text
+/// part2:
+///   #..#
+///   ####
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Check {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "fail",
+            Check::Missing => "missing",
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        let mut lines = text.lines().enumerate().peekable();
+
+        while let Some((index, line)) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(':')
+                .filter(|_| !line.starts_with(char::is_whitespace))
+                .ok_or_else(|| format!("line {}: expected 'partN: answer'", index + 1))?;
+
+            let mut value = value.trim().to_string();
+
+            // The rows of a multi-line answer, without the indent of the first of them.
+            if value.is_empty() {
+                let mut rows = Vec::new();
+                let mut indent = "";
+
+                while let Some((_, row)) =
+                    lines.next_if(|(_, x)| x.starts_with(char::is_whitespace))
+                {
+                    if rows.is_empty() {
+                        indent = &row[..(row.len() - row.trim_start().len())];
+                    }
+
+                    rows.push(
+                        row.strip_prefix(indent)
+                            .unwrap_or(row.trim_start())
+                            .trim_end(),
+                    );
+                }
+
+                value = rows.join("\n");
+            }
+
+            let value = Some(value);
+
+            match key.trim() {
+                "part1" => answers.part1 = value,
+                "part2" => answers.part2 = value,
+                x => return Err(format!("line {}: unknown part '{}'", index + 1, x)),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// A day without an answers file just has nothing to check against yet.
+    pub fn load(dir: &Path, day: u8) -> Result<Self, String> {
+        Answers::read(&dir.join(format!("day{:02}", day)))
+    }
+
+    /// Reads the answers in `path`, which are all missing if there is no such file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text)
+                .map_err(|e| format!("bad answers file '{}': {}", path.display(), e)),
+            Err(e) if io::ErrorKind::NotFound == e.kind() => Ok(Answers::default()),
+            Err(e) => Err(format!(
+                "could not read answers '{}': {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Parts that were not run, or are not solved yet, are not checked.
+    pub fn check(
+        &self,
+        (part1, part2): &(Option<Solution>, Option<Solution>),
+    ) -> (Option<Check>, Option<Check>) {
+        let solved = |x: &&Solution| !x.is_unsolved();
+
+        (
+            part1.as_ref().filter(solved).map(|x| check(&self.part1, x)),
+            part2.as_ref().filter(solved).map(|x| check(&self.part2, x)),
+        )
+    }
+}
+
+fn check(expected: &Option<String>, actual: &Solution) -> Check {
+    match expected {
+        Some(expected) if *expected == actual.to_string() => Check::Pass,
+        Some(expected) => Check::Fail {
+            expected: expected.clone(),
+        },
+        None => Check::Missing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Check};
+    use crate::Solution;
+
+    #[test]
+    fn it_works() {
+        let answers = Answers::parse("part1: 54561\n\n").unwrap();
+
+        assert_eq!(
+            answers.check(&(Some(Solution::U32(54561)), Some(Solution::U32(1)))),
+            (Some(Check::Pass), Some(Check::Missing))
+        );
+
+        let answers = Answers::parse("part1: 1\npart2: 2\n").unwrap();
+
+        assert_eq!(
+            answers.check(&(Some(Solution::U32(3)), None)),
+            (
+                Some(Check::Fail {
+                    expected: "1".to_string()
+                }),
+                None
+            )
+        );
+
+        assert_eq!(
+            answers.check(&(Some(Solution::Unsolved), Some(Solution::U32(2)))),
+            (None, Some(Check::Pass))
+        );
+
+        assert!(Answers::parse("part3: 1").is_err());
+        assert!(Answers::parse("  #..#").is_err());
+
+        let answers = Answers::parse("part1: 7\npart2:\n  #..#\n  ####\n").unwrap();
+        let picture = Solution::Lines(vec!["#..#".to_string(), "####".to_string()]);
+
+        assert_eq!(answers.part2.as_deref(), Some("#..#\n####"));
+        assert_eq!(
+            answers.check(&(Some(Solution::U32(7)), Some(picture))),
+            (Some(Check::Pass), Some(Check::Pass))
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::runner::{self, as_ms, Parts};
+use crate::{Day, ParseError};
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::time::Duration;
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+
+        samples.sort();
+
+        let len = samples.len();
+
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / len as u32;
+
+        // Use the sample (n - 1) variance, since we only ever see a handful of the possible runs.
+        let variance = if 1 == len {
+            0.0
+        } else {
+            samples
+                .iter()
+                .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / (len - 1) as f64
+        };
+
+        // Nearest-rank percentile.
+        let p95 = samples[(len * 95).div_ceil(100) - 1];
+
+        Stats {
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.4} ms, median {:.4} ms, mean {:.4} ms, stddev {:.4} ms, p95 {:.4} ms",
+            as_ms(self.min),
+            as_ms(self.median),
+            as_ms(self.mean),
+            as_ms(self.stddev),
+            as_ms(self.p95)
+        )
+    }
+}
+
+pub struct Bench {
+    pub runs: usize,
+    pub parse: Option<Stats>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    pub total: Stats,
+}
+
+fn stats(samples: Vec<Duration>) -> Option<Stats> {
+    (!samples.is_empty()).then(|| Stats::new(samples))
+}
+
+pub fn bench(day: &dyn Day, input: &str, parts: Parts, runs: usize) -> Result<Bench, ParseError> {
+    assert!(0 < runs);
+
+    // Warm up the caches (and the branch predictors) before we record anything.
+    for _ in 0..(runs / 10).max(1) {
+        black_box(runner::run(day, input, parts, false)?);
+    }
+
+    let mut parse = Vec::new();
+    let mut part1 = Vec::new();
+    let mut part2 = Vec::new();
+    let mut total = Vec::new();
+
+    for _ in 0..runs {
+        let timings = black_box(runner::run(day, input, parts, false)?).timings;
+
+        parse.extend(timings.parse);
+        part1.extend(timings.part1);
+        part2.extend(timings.part2);
+        total.push(timings.total());
+    }
+
+    Ok(Bench {
+        runs,
+        parse: stats(parse),
+        part1: stats(part1),
+        part2: stats(part2),
+        total: Stats::new(total),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let samples = (1..=20).map(Duration::from_millis).collect();
+
+        let stats = super::Stats::new(samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.mean, Duration::from_micros(10500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.stddev.as_micros(), 5916);
+    }
+}
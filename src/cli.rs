@@ -0,0 +1,298 @@
+use advent_of_code_template::etc::params;
+use advent_of_code_template::input::InputSource;
+use advent_of_code_template::runner::{Options, Parts, DEFAULT_TIMEOUT};
+use advent_of_code_template::{days, examples};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub const USAGE: &str = concat!(
+    "Usage: ",
+    env!("CARGO_PKG_NAME"),
+    " [options] <days>...
+
+Days can be single days (5), ranges (3-7), comma separated lists (1,3,5) or 'all'.
+
+Options:
+  --part <1|2>          Only run the given part of each day
+  --input <file>        Read the input for a single day from <file>, or stdin for '-'
+  --input-dir <dir>     Read each day's input from <dir>/dayXX (default: input)
+  --format <format>     Report as text, json or csv (default: text)
+  --jobs <n>            Run up to <n> days at the same time (default: 1)
+  --parallel-parts      Run both parts of a day at the same time
+  --timeout <secs>      Give up on a day after <secs> seconds, or 'none' (default: 60)
+  --bench <runs>        Benchmark each day over <runs> runs
+  --verify              Check the answers against answers/dayXX
+  --answers-dir <dir>   Check the answers against <dir>/dayXX
+  --example             Run on the examples in examples/dayXX-N instead of the input
+  --check-input         Report every problem with each day's input instead of solving it
+  --param <key=value>   Set a parameter for a day, like day02.bag='12 red, 13 green, 14 blue'
+  --config <file>       Read 'key = value' parameters from <file> (--param overrides them)
+  -v, --verbose         Let days log what they are doing to stderr
+  --list                List the days and whether they are implemented
+  -h, --help            Show this help
+"
+);
+
+pub enum Command {
+    Run(Options),
+    /// Validate each day's input instead of solving it.
+    CheckInput(Options),
+    List,
+    Help,
+}
+
+fn value(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+    what: &str,
+) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("{} needs {}", flag, what))
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    let number = day
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| format!("Not a valid day: '{}'", day))?;
+
+    if days::get(number).is_none() {
+        return Err(format!(
+            "There is no day {} (days run from 1 to 25)",
+            number
+        ));
+    }
+
+    Ok(number)
+}
+
+/// Parses `all`, single days, ranges like `3-7` and comma separated lists of those.
+fn parse_days(arg: &str, days: &mut Vec<u8>) -> Result<(), String> {
+    for piece in arg.split(',') {
+        let new_days = if "all" == piece {
+            days::ALL.iter().map(|x| x.number()).collect()
+        } else if let Some((start, end)) = piece.split_once('-') {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+
+            if start > end {
+                return Err(format!("Not a valid range of days: '{}'", piece));
+            }
+
+            (start..=end).collect()
+        } else {
+            vec![parse_day(piece)?]
+        };
+
+        for day in new_days {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut days = Vec::new();
+    let mut source = None;
+    let mut parts = Parts::default();
+    let mut format = Default::default();
+    let mut jobs = 1;
+    let mut parallel_parts = false;
+    let mut timeout = Some(DEFAULT_TIMEOUT);
+    let mut bench = None;
+    let mut answers = None;
+    let mut examples = None;
+    let mut check_input = false;
+    let mut verbose = false;
+    let mut config = Vec::new();
+    let mut params = Vec::new();
+
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "--part" => {
+                parts = match value(&mut args, "--part", "1 or 2")?.as_str() {
+                    "1" => Parts::Part1,
+                    "2" => Parts::Part2,
+                    x => return Err(format!("Not a valid part: '{}' (expected 1 or 2)", x)),
+                }
+            }
+            "--input" => {
+                let path = value(&mut args, "--input", "a file path or '-'")?;
+
+                source = Some(if "-" == path {
+                    InputSource::Stdin
+                } else {
+                    InputSource::File(PathBuf::from(path))
+                });
+            }
+            "--input-dir" => {
+                let path = value(&mut args, "--input-dir", "a directory path")?;
+                source = Some(InputSource::Directory(PathBuf::from(path)));
+            }
+            "--format" => {
+                format = value(&mut args, "--format", "one of text, json or csv")?.parse()?;
+            }
+            "--jobs" => {
+                let n = value(&mut args, "--jobs", "a number of threads")?;
+
+                jobs = match n.parse::<usize>() {
+                    Ok(n) if 0 < n => n,
+                    _ => return Err(format!("Not a valid number of jobs: '{}'", n)),
+                };
+            }
+            "--parallel-parts" => parallel_parts = true,
+            "--timeout" => {
+                let secs = value(&mut args, "--timeout", "a number of seconds")?;
+
+                timeout = match secs.parse::<f64>() {
+                    _ if "none" == secs => None,
+                    Ok(secs) if secs.is_finite() && 0.0 < secs => {
+                        Some(Duration::from_secs_f64(secs))
+                    }
+                    _ => return Err(format!("Not a valid timeout: '{}'", secs)),
+                };
+            }
+            "--bench" => {
+                let runs = value(&mut args, "--bench", "a number of runs")?;
+
+                bench = match runs.parse::<usize>() {
+                    Ok(runs) if 0 < runs => Some(runs),
+                    _ => return Err(format!("Not a valid number of runs: '{}'", runs)),
+                };
+            }
+            "--verify" => {
+                answers.get_or_insert_with(|| PathBuf::from("answers"));
+            }
+            "--answers-dir" => {
+                let path = value(&mut args, "--answers-dir", "a directory path")?;
+                answers = Some(PathBuf::from(path));
+            }
+            "--check-input" => check_input = true,
+            "-v" | "--verbose" => verbose = true,
+            "--param" => {
+                params.push(params::parse_param(&value(
+                    &mut args,
+                    "--param",
+                    "key=value",
+                )?)?);
+            }
+            "--config" => {
+                let path = value(&mut args, "--config", "a file path")?;
+
+                let text = fs::read_to_string(&path)
+                    .map_err(|e| format!("could not read config '{}': {}", path, e))?;
+
+                config.extend(
+                    params::parse_config(&text)
+                        .map_err(|e| format!("bad config '{}': {}", path, e))?,
+                );
+            }
+            "--example" => examples = Some(PathBuf::from(examples::DEFAULT_DIR)),
+            x if x.starts_with('-') && 1 < x.len() => return Err(format!("Unknown option: {}", x)),
+            x => parse_days(x, &mut days)?,
+        }
+    }
+
+    if days.is_empty() {
+        return Err("No days given; pass day numbers, ranges like 3-7, or 'all'".to_string());
+    }
+
+    if source.is_some() && examples.is_some() {
+        return Err("--example can't be combined with --input or --input-dir".to_string());
+    }
+
+    let source = source.unwrap_or_default();
+
+    if matches!(source, InputSource::File(_) | InputSource::Stdin) && 1 != days.len() {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+
+    // Anything else running at the same time would skew the timings.
+    if bench.is_some() && (1 < jobs || parallel_parts) {
+        return Err("--bench can't be combined with --jobs or --parallel-parts".to_string());
+    }
+
+    if check_input && examples.is_some() {
+        return Err("--check-input can't be combined with --example".to_string());
+    }
+
+    let options = Options {
+        days,
+        source,
+        parts,
+        format,
+        jobs,
+        parallel_parts,
+        timeout,
+        bench,
+        answers,
+        examples,
+        verbose,
+        // Later values win, so the command line overrides the config.
+        params: config.into_iter().chain(params).collect(),
+    };
+
+    if check_input {
+        Ok(Command::CheckInput(options))
+    } else {
+        Ok(Command::Run(options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Command};
+    use advent_of_code_template::runner::Parts;
+
+    fn days(args: &str) -> Result<Vec<u8>, String> {
+        match parse(args.split(' ').map(str::to_string))? {
+            Command::Run(options) => Ok(options.days),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(days("5"), Ok(vec![5]));
+        assert_eq!(days("3-7 1"), Ok(vec![3, 4, 5, 6, 7, 1]));
+        assert_eq!(days("1,3,5 3"), Ok(vec![1, 3, 5]));
+        assert_eq!(days("all").unwrap().len(), 25);
+
+        assert!(days("0").is_err());
+        assert!(days("26").is_err());
+        assert!(days("7-3").is_err());
+        assert!(days("x").is_err());
+        assert!(days("--nope 1").is_err());
+        assert!(days("--part 3 1").is_err());
+        assert!(days("--input a 1 2").is_err());
+        assert!(days("--input - 1 2").is_err());
+        assert!(days("--param x 1").is_err());
+        assert!(days("--config does/not/exist 1").is_err());
+        assert!(days("--jobs 0 1").is_err());
+        assert!(days("--timeout -1 1").is_err());
+        assert!(days("--timeout none 1").is_ok());
+        assert!(days("--jobs 2 --bench 10 1").is_err());
+        assert!(days("--example --input a 1").is_err());
+        assert!(parse(["--check-input", "--example", "1"].map(str::to_string)).is_err());
+
+        assert!(matches!(
+            parse(["--check-input", "1"].map(str::to_string)),
+            Ok(Command::CheckInput(_))
+        ));
+        assert!(parse(Vec::new()).is_err());
+
+        match parse(["--part", "2", "1"].map(str::to_string)) {
+            Ok(Command::Run(options)) => assert_eq!(options.parts, Parts::Part2),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,107 +1,152 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::line_problems;
+use crate::etc::{lines, Line, Matcher};
+use crate::{verbose, Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let mut sol1 = 0;
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// The value of one line, made of the first and last digits on it that `matcher` finds. `words`
+/// are the spelled out digits it looks for as well.
+fn calibration(
+    line: &Line,
+    matcher: &Matcher<u32>,
+    words: &[(&str, u32)],
+) -> Result<u32, ParseError> {
+    let mut found = matcher.find_iter(line.text);
+
+    let Some(first) = found.next() else {
+        let expected = if words.is_empty() {
+            "expected a digit"
+        } else {
+            "expected a digit or a digit spelled out"
+        };
 
-    for line in input.lines() {
-        println!("{}", line);
+        return Err(line.error(line.text, expected));
+    };
+
+    // Matches come out in the order they end, and with overlaps a later match can start
+    // before an earlier one ends, so compare by where they start.
+    let (first, last) = found.fold((first, first), |(first, last), x| {
+        (first.min(x), last.max(x))
+    });
+
+    verbose!(
+        "line {}: first {} at {}, last {} at {}",
+        line.index + 1,
+        first.1,
+        first.0,
+        last.1,
+        last.0
+    );
+
+    Ok(first.1 * 10 + last.1)
+}
 
-        let first = line.chars().find(|x| x.is_ascii_digit()).unwrap();
-        let last = line.chars().rev().find(|x| x.is_ascii_digit()).unwrap();
+/// The sum of the calibration values, where each line's value is made of the first and last
+/// digits on it. Digits can also be spelled out using any of `words`, like `("zero", 0)` or
+/// `("drei", 3)`, and spelled out digits can overlap, so "twone" is 2 then 1.
+pub fn solver(input: &str, words: &[(&str, u32)]) -> Result<Solution, ParseError> {
+    let matcher = Matcher::new(DIGITS.iter().chain(words).copied());
 
-        let v = (first.to_digit(10).unwrap() * 10) + last.to_digit(10).unwrap();
+    let mut sum = 0;
 
-        sol1 += v;
+    for line in lines(input) {
+        sum += calibration(&line, &matcher, words)?;
     }
 
-    Solution::U32(sol1)
+    Ok(Solution::U32(sum))
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let mut sol2 = 0;
-
-    for line in input.lines() {
-        let variants = [
-            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-        ];
-
-        let first_index = variants.iter().fold(usize::MAX, |acc, x| {
-            acc.min(line.find(x).unwrap_or(usize::MAX))
-        });
-        let first_index = first_index.min(
-            line.find(|x: char| x.is_ascii_digit())
-                .unwrap_or(usize::MAX),
-        );
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    solver(input, &[])
+}
 
-        let last_index = variants.iter().fold(usize::MIN, |acc, x| {
-            acc.max(line.rfind(x).unwrap_or(usize::MIN))
-        });
-        let last_index = last_index.max(
-            line.rfind(|x: char| x.is_ascii_digit())
-                .unwrap_or(usize::MIN),
-        );
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    solver(input, &WORDS)
+}
 
-        let parser = |x: &str| {
-            println!("Parser found '{}'", x);
-
-            if x.starts_with("one") {
-                1
-            } else if x.starts_with("two") {
-                2
-            } else if x.starts_with("three") {
-                3
-            } else if x.starts_with("four") {
-                4
-            } else if x.starts_with("five") {
-                5
-            } else if x.starts_with("six") {
-                6
-            } else if x.starts_with("seven") {
-                7
-            } else if x.starts_with("eight") {
-                8
-            } else if x.starts_with("nine") {
-                9
-            } else {
-                x.chars().next().unwrap().to_digit(10).unwrap()
-            }
-        };
+pub struct Day01;
 
-        let first = parser(&line[first_index..]);
-        let last = parser(&line[last_index..]);
+impl Day for Day01 {
+    fn number(&self) -> u8 {
+        1
+    }
 
-        let v = (first * 10) + last;
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
 
-        sol2 += v;
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
     }
 
-    Solution::U32(sol2)
-}
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day01"));
+    /// Every line without a digit, since part 1 can't read those whatever part 2 makes of them.
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        let matcher = Matcher::new(DIGITS);
 
-    (solve1(input), solve2(input))
+        line_problems(input, |line| calibration(line, &matcher, &[]))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE1: &str = include_str!("../../examples/day01-1");
+    const EXAMPLE2: &str = include_str!("../../examples/day01-2");
+
     #[test]
     fn it_works() {
-        let r = super::solve2(
-            r"two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen",
+        assert_eq!(super::solve1(EXAMPLE1), Ok(Solution::U32(142)));
+
+        let r = super::solve2(EXAMPLE2);
+
+        assert_eq!(r, Ok(Solution::U32(281)));
+
+        assert_eq!(super::solve1("1a\nabc").unwrap_err().line, 2);
+
+        let problems = super::Day01.validate("1a\nabc\n2\none");
+
+        assert_eq!(problems.iter().map(|x| x.line).collect::<Vec<_>>(), [2, 4]);
+
+        assert_eq!(
+            super::solve2("eightwo\ntwone\noneight"),
+            Ok(Solution::U32(82 + 21 + 18))
         );
 
-        assert_eq!(r, Solution::U32(281));
+        let words = [("zero", 0), ("eins", 1), ("zwei", 2), ("drei", 3)];
+
+        assert_eq!(
+            super::solver("zweiabc\nzerodreins", &words),
+            Ok(Solution::U32(22 + 1))
+        );
     }
 }
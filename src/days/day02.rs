@@ -1,106 +1,261 @@
-use core::panic;
+use std::collections::HashMap;
+use std::str::FromStr;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::{key_value, line_problems};
+use crate::etc::{lines, params, Line};
+use crate::{Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-// 12 red cubes, 13 green cubes, and 14 blue cubes max!
-fn is_over(line: &str) -> bool {
-    for draw in line.split(';') {
-        for cubes in draw.split(',') {
-            let (number, colour) = cubes.trim().split_once(' ').unwrap();
+/// Any colour of cube, not just red, green and blue.
+pub type Colour<'a> = &'a str;
 
-            let number = number.parse::<i32>().unwrap();
+/// The colours part 2 multiplies together, whatever the bag part 1 asks about holds.
+pub const COLOURS: [Colour; 3] = ["red", "green", "blue"];
 
-            let colour = match colour {
-                "blue" => 14,
-                "red" => 12,
-                "green" => 13,
-                _ => panic!(),
-            };
+/// The parameter that replaces the bag part 1 asks about, like `12 red, 13 green, 14 blue`.
+pub const BAG_PARAM: &str = "day02.bag";
 
-            if number > colour {
-                return false;
-            }
+/// How many cubes of each colour are in a bag. Colours that aren't in it have none.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag {
+    cubes: HashMap<String, u32>,
+}
+
+impl Bag {
+    /// The bag part 1 asks about.
+    pub fn puzzle() -> Self {
+        "12 red, 13 green, 14 blue".parse().unwrap()
+    }
+
+    /// The bag given with the `day02.bag` parameter, or else the puzzle's.
+    pub fn from_params() -> Result<Self, ParseError> {
+        Bag::from_param(params::get(BAG_PARAM).as_deref())
+    }
+
+    /// The bag written in `param`, or the puzzle's if there isn't one.
+    pub fn from_param(param: Option<&str>) -> Result<Self, ParseError> {
+        match param {
+            Some(bag) => bag.parse().map_err(|e: ParseError| ParseError {
+                message: format!("bad parameter '{}': {}", BAG_PARAM, e.message),
+                ..e
+            }),
+            None => Ok(Bag::puzzle()),
         }
     }
 
-    true
+    pub fn get(&self, colour: Colour) -> u32 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = Colour<'_>> {
+        self.cubes.keys().map(String::as_str)
+    }
+
+    /// The number of cubes of each of `colours` multiplied together, so any colour that isn't in
+    /// the bag makes the power zero.
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = Colour<'a>>) -> u32 {
+        colours.into_iter().map(|x| self.get(x)).product()
+    }
 }
 
-pub fn solve1(input: &str) -> Solution {
-    let mut sol = 0;
+impl FromStr for Bag {
+    type Err = ParseError;
 
-    for line in input.lines() {
-        let line = line.strip_prefix("Game ").unwrap();
+    /// Parses a bag written like a draw, e.g. `12 red, 13 green, 14 blue`.
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut cubes = HashMap::new();
 
-        let (id, line) = line.split_once(':').unwrap();
+        for cube in s.split(',') {
+            let (number, colour) = cube.trim().split_once(' ').ok_or_else(|| {
+                ParseError::elsewhere(cube.trim(), "expected a number and a colour")
+            })?;
 
-        let id = id.parse::<u32>().unwrap();
+            let number = number
+                .parse()
+                .map_err(|_| ParseError::elsewhere(number, "expected a number"))?;
 
-        if is_over(line) {
-            sol += id;
+            cubes.insert(colour.trim().to_string(), number);
         }
+
+        Ok(Bag { cubes })
     }
+}
 
-    Solution::U32(sol)
+/// A game of handfuls of cubes drawn from a bag.
+pub struct Game<'a> {
+    pub id: u32,
+    pub draws: Vec<HashMap<Colour<'a>, u32>>,
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let mut sol = 0;
+impl<'a> Game<'a> {
+    pub fn parse(line: &Line<'a>) -> Result<Self, ParseError> {
+        let (id, text) = key_value(line, "Game")?;
 
-    for line in input.lines() {
-        let line = line.strip_prefix("Game ").unwrap();
+        let mut draws = Vec::new();
 
-        let (_, line) = line.split_once(':').unwrap();
+        for draw in text.split(';') {
+            let mut cubes = HashMap::new();
 
-        let mut blue = 0;
-        let mut green = 0;
-        let mut red = 0;
+            for cube in draw.split(',') {
+                let (number, colour) = line.split_once(cube.trim(), ' ')?;
 
-        for draw in line.split(';') {
-            for cubes in draw.split(',') {
-                let (number, colour) = cubes.trim().split_once(' ').unwrap();
+                *cubes.entry(colour).or_insert(0) += line.number::<u32>(number)?;
+            }
 
-                let number = number.parse::<u32>().unwrap();
+            draws.push(cubes);
+        }
 
-                match colour {
-                    "blue" => blue = blue.max(number),
-                    "red" => red = red.max(number),
-                    "green" => green = green.max(number),
-                    _ => panic!(),
-                }
-            }
+        Ok(Game { id, draws })
+    }
+
+    /// The fewest cubes of each colour the bag could have held.
+    pub fn minimal_bag(&self) -> Bag {
+        let mut bag = Bag::default();
+
+        for (colour, number) in self.draws.iter().flatten() {
+            let most = bag.cubes.entry(colour.to_string()).or_insert(0);
+
+            *most = (*most).max(*number);
         }
 
-        sol += blue * green * red;
+        bag
+    }
+
+    /// The indices of the draws that take more cubes of some colour than `bag` holds.
+    pub fn violations(&self, bag: &Bag) -> Vec<usize> {
+        self.draws
+            .iter()
+            .enumerate()
+            .filter(|(_, draw)| {
+                draw.iter()
+                    .any(|(colour, number)| bag.get(colour) < *number)
+            })
+            .map(|(index, _)| index)
+            .collect()
     }
 
-    Solution::U32(sol)
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.violations(bag).is_empty()
+    }
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day02"));
+/// The sum of the ids of the games that could have been played with `bag`.
+pub fn solver1(input: &str, bag: &Bag) -> Result<Solution, ParseError> {
+    let mut sol = 0;
+
+    for line in lines(input) {
+        let game = Game::parse(&line)?;
+
+        if game.is_possible(bag) {
+            sol += game.id;
+        }
+    }
 
-    (solve1(input), solve2(input))
+    Ok(Solution::U32(sol))
+}
+
+/// The sum of the power of each game's minimal bag, over `colours`.
+pub fn solver2(input: &str, colours: &[Colour]) -> Result<Solution, ParseError> {
+    let mut sol = 0;
+
+    for line in lines(input) {
+        sol += Game::parse(&line)?
+            .minimal_bag()
+            .power(colours.iter().copied());
+    }
+
+    Ok(Solution::U32(sol))
+}
+
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    solver1(input, &Bag::puzzle())
+}
+
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    solver2(input, &COLOURS)
+}
+
+pub struct Day02;
+
+impl Day for Day02 {
+    fn number(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solver1(input, &Bag::from_params()?)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        line_problems(input, Game::parse)
+    }
+
+    fn check_params(&self) -> Result<(), String> {
+        Bag::from_params().map(|_| ()).map_err(|e| e.to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day02-1");
+
     #[test]
     fn it_works() {
-        let input = r"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let input = EXAMPLE;
         let s1 = super::solve1(input);
 
         let s2 = super::solve2(input);
 
-        assert_eq!(s1, Solution::U32(8));
-        assert_eq!(s2, Solution::U32(2286));
+        assert_eq!(s1, Ok(Solution::U32(8)));
+        assert_eq!(s2, Ok(Solution::U32(2286)));
+
+        let error = super::solve1("Game 1: 3 blue\nGame 2: x purple").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 9, "x"));
+
+        let line = etc::lines("Game 7: 3 blue, 2 purple; 1 red; 5 red, 1 blue")
+            .next()
+            .unwrap();
+        let game = super::Game::parse(&line).unwrap();
+
+        assert_eq!(game.violations(&super::Bag::puzzle()), [0]);
+        assert_eq!(
+            game.minimal_bag().power(["red", "blue", "purple"]),
+            5 * 3 * 2
+        );
+
+        let bag: super::Bag = "5 red, 3 blue, 2 purple".parse().unwrap();
+
+        assert!(game.is_possible(&bag));
+        assert_eq!(super::solver1(input, &bag), Ok(Solution::U32(0)));
+        assert_eq!(
+            super::solver2(input, &["red", "purple"]),
+            Ok(Solution::U32(0))
+        );
+        assert_eq!(
+            super::solver2(input, &["red"]),
+            Ok(Solution::U32(4 + 1 + 20 + 14 + 6))
+        );
+
+        assert!("12 red, x blue".parse::<super::Bag>().is_err());
+
+        let error = super::Bag::from_param(Some("12 red, x blue")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "bad parameter 'day02.bag': expected a number (found 'x')"
+        );
+        assert_eq!(super::Bag::from_param(None), Ok(super::Bag::puzzle()));
     }
 }
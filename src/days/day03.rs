@@ -1,239 +1,206 @@
-use std::collections::HashMap;
+use std::ops::Range;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::{Grid, Point};
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let mut sol = 0;
-
-    let mut len = None;
-
-    let mut board = Vec::new();
-
-    for line in input.lines() {
-        if let Some(len) = len {
-            assert_eq!(len, line.len() + 2);
-        } else {
-            // +2 for the edges!
-            len = Some(line.len() + 2);
-
-            // Before the first row we spam in a lot of empty tiles ('.').
-            board.resize(len.unwrap(), '.');
-        }
-
-        // Push a left hand boundary.
-        board.push('.');
+/// A number on the schematic, which covers `columns` of `row`.
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub columns: Range<usize>,
+    /// The indices of the symbols next to it.
+    pub symbols: Vec<usize>,
+}
 
-        for c in line.chars() {
-            board.push(c);
-        }
+/// Anything on the schematic that isn't a digit or a '.'.
+pub struct Symbol {
+    pub symbol: char,
+    pub position: Point<usize>,
+    /// The indices of the numbers next to it. Each number is only here once, however many of
+    /// its digits touch the symbol.
+    pub numbers: Vec<usize>,
+}
 
-        // Push a right hand boundary.
-        board.push('.');
-    }
+/// The engine schematic, read without changing it, where every number knows the symbols next to
+/// it and every symbol knows the numbers. Both parts are queries over this, and the schematic
+/// doesn't have to be square.
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+}
 
-    let len = len.unwrap();
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let board = Grid::parse(input, Some)?;
 
-    // After the last row we spam in a lot of empty tiles ('.').
-    board.resize(board.len() + len, '.');
+        let mut numbers = Vec::new();
 
-    // Only loop over the non-boundary tiles.
-    for y in 1..(len - 1) {
-        for x in 1..(len - 1) {
-            let index = y * len + x;
+        // Which number (if any) covers each cell, to look the neighbours of symbols up in.
+        let mut covered = Grid::filled(board.width(), board.height(), None);
 
-            let tile = board[index];
+        for (y, row) in board.rows().enumerate() {
+            let mut x = 0;
 
-            // We are only looking for actual symbols here.
-            if tile.is_ascii_digit() || '.' == tile {
-                continue;
-            }
+            while x < row.len() {
+                let len = row[x..].iter().take_while(|x| x.is_ascii_digit()).count();
 
-            for other_index in [
-                index - len - 1,
-                index - len,
-                index - len + 1,
-                index - 1,
-                index + 1,
-                index + len - 1,
-                index + len,
-                index + len + 1,
-            ] {
-                let other = board[other_index];
-
-                // Only looking for digits here.
-                if !other.is_ascii_digit() {
+                if 0 == len {
+                    x += 1;
                     continue;
                 }
 
-                // We need to go left on the board until we hit a non digit.
-                let mut start_index = other_index;
+                let columns = x..(x + len);
 
-                while board[start_index].is_ascii_digit() {
-                    start_index -= 1;
-                }
-
-                // We'll be one behind the actual start, so bump now (and un-mut ourselves).
-                let start_index = start_index + 1;
+                let value = row[columns.clone()]
+                    .iter()
+                    .fold(0, |acc, x| acc * 10 + x.to_digit(10).unwrap());
 
-                let mut end_index = other_index;
-
-                while board[end_index].is_ascii_digit() {
-                    end_index += 1;
+                for covered in &mut covered.row_mut(y)[columns.clone()] {
+                    *covered = Some(numbers.len());
                 }
 
-                let mut part_number = 0;
-
-                for tile in board.iter_mut().take(end_index).skip(start_index) {
-                    part_number *= 10;
-                    part_number += tile.to_digit(10).unwrap();
+                numbers.push(Number {
+                    value,
+                    row: y,
+                    columns,
+                    symbols: Vec::new(),
+                });
 
-                    // Wipe out the part number so that we don't double count!
-                    *tile = '.';
-                }
-
-                sol += part_number;
+                x += len;
             }
         }
-    }
-
-    Solution::U32(sol)
-}
 
-pub fn solve2(input: &str) -> Solution {
-    let mut len = None;
+        let mut symbols = Vec::new();
 
-    let mut board = Vec::new();
-
-    let mut gears = HashMap::new();
+        for (position, symbol) in board.iter() {
+            if symbol.is_ascii_digit() || '.' == *symbol {
+                continue;
+            }
 
-    for line in input.lines() {
-        if let Some(len) = len {
-            assert_eq!(len, line.len() + 2);
-        } else {
-            // +2 for the edges!
-            len = Some(line.len() + 2);
+            let mut neighbours: Vec<usize> = board
+                .neighbours8(position)
+                .filter_map(|x| covered[x])
+                .collect();
 
-            // Before the first row we spam in a lot of empty tiles ('.').
-            board.resize(len.unwrap(), '.');
-        }
+            neighbours.sort();
+            neighbours.dedup();
 
-        // Push a left hand boundary.
-        board.push('.');
+            for number in &neighbours {
+                numbers[*number].symbols.push(symbols.len());
+            }
 
-        for c in line.chars() {
-            board.push(c);
+            symbols.push(Symbol {
+                symbol: *symbol,
+                position,
+                numbers: neighbours,
+            });
         }
 
-        // Push a right hand boundary.
-        board.push('.');
+        Ok(Schematic { numbers, symbols })
     }
 
-    let len = len.unwrap();
-
-    // After the last row we spam in a lot of empty tiles ('.').
-    board.resize(board.len() + len, '.');
-
-    // Only loop over the non-boundary tiles.
-    for y in 1..(len - 1) {
-        for x in 1..(len - 1) {
-            let index = y * len + x;
-
-            let tile = board[index];
-
-            // We are only looking for gears.
-            if '*' != tile {
-                continue;
-            }
-
-            for other_index in [
-                index - len - 1,
-                index - len,
-                index - len + 1,
-                index - 1,
-                index + 1,
-                index + len - 1,
-                index + len,
-                index + len + 1,
-            ] {
-                let other = board[other_index];
-
-                // Only looking for digits here.
-                if !other.is_ascii_digit() {
-                    continue;
-                }
-
-                // We need to go left on the board until we hit a non digit.
-                let mut start_index = other_index;
-
-                while board[start_index].is_ascii_digit() {
-                    start_index -= 1;
-                }
-
-                // We'll be one behind the actual start, so bump now (and un-mut ourselves).
-                let start_index = start_index + 1;
+    /// The numbers next to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().filter(|x| !x.symbols.is_empty())
+    }
 
-                let mut end_index = other_index;
+    /// Each `symbol` with exactly `count` numbers next to it, along with those numbers
+    /// multiplied together.
+    pub fn gears(&self, symbol: char, count: usize) -> impl Iterator<Item = (&Symbol, u32)> {
+        self.symbols
+            .iter()
+            .filter(move |x| symbol == x.symbol && count == x.numbers.len())
+            .map(|x| {
+                let ratio = x.numbers.iter().map(|x| self.numbers[*x].value).product();
+
+                (x, ratio)
+            })
+    }
+}
 
-                while board[end_index].is_ascii_digit() {
-                    end_index += 1;
-                }
+impl Parsed for Schematic {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U32(self.part_numbers().map(|x| x.value).sum()))
+    }
 
-                let mut part_number = 0;
+    fn part2(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U32(
+            self.gears('*', 2).map(|(_, ratio)| ratio).sum(),
+        ))
+    }
+}
 
-                for tile in board.iter_mut().take(end_index).skip(start_index) {
-                    part_number *= 10;
-                    part_number += tile.to_digit(10).unwrap();
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    Schematic::parse(input)?.part1()
+}
 
-                    // Wipe out the part number so that we don't double count!
-                    *tile = '.';
-                }
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    Schematic::parse(input)?.part2()
+}
 
-                gears.entry(index).or_insert_with(Vec::new);
+pub struct Day03;
 
-                gears.get_mut(&index).unwrap().push(part_number);
-            }
-        }
+impl Day for Day03 {
+    fn number(&self) -> u8 {
+        3
     }
 
-    let mut sol = 0;
-
-    for (_, gear) in gears {
-        if 2 != gear.len() {
-            continue;
-        }
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
 
-        sol += gear.iter().product::<u32>();
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
     }
 
-    Solution::U32(sol)
-}
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day03"));
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Schematic::parse(input)?)))
+    }
 
-    (solve1(input), solve2(input))
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        Grid::<char>::problems(input, |_| true)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::etc::Point;
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day03-1");
+
     #[test]
     fn it_works() {
-        let input = r"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-        assert_eq!(super::solve1(input), Solution::U32(4361));
-        assert_eq!(super::solve2(input), Solution::U32(467835));
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(4361)));
+        assert_eq!(super::solve2(input), Ok(Solution::U32(467835)));
+
+        assert_eq!(super::solve1("467..\n...*").unwrap_err().line, 2);
+
+        // Wider than it is tall, with a number touching a symbol twice and one touching two.
+        let schematic = super::Schematic::parse("12....#.\n.*...7..\n45..*..9").unwrap();
+
+        let numbers: Vec<_> = schematic.numbers.iter().map(|x| x.value).collect();
+
+        assert_eq!(numbers, [12, 7, 45, 9]);
+        assert_eq!(schematic.numbers[2].columns, 0..2);
+        assert_eq!(schematic.part_numbers().count(), 3);
+        assert_eq!(schematic.symbols[1].numbers, [0, 2]);
+        assert_eq!(schematic.numbers[1].symbols, [0, 2]);
+
+        let gears: Vec<_> = schematic
+            .gears('*', 2)
+            .map(|(x, ratio)| (x.position, ratio))
+            .collect();
+
+        assert_eq!(gears, [(Point::new(1, 1), 540)]);
+        assert_eq!(schematic.gears('*', 1).count(), 1);
     }
 }
@@ -1,128 +1,207 @@
-use std::collections::{HashMap, HashSet};
-
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::{key_value, line_problems};
+use crate::etc::{lines, Line};
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let mut sol = 0;
+/// A set of the numbers on a card as a bitset, since they're all under 128.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Numbers(u128);
 
-    for line in input.lines() {
-        let mut winners = HashSet::new();
+impl Numbers {
+    /// The whitespace separated numbers in `text`, which should be a slice of `line`.
+    pub fn parse<'a>(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        let mut bits = 0;
 
-        let (_, line) = line.split_once(':').unwrap();
-        let (winning_numbers, my_numbers) = line.split_once('|').unwrap();
+        for number in text.split_whitespace() {
+            let value: u8 = line.number(number)?;
 
-        for winning_number in winning_numbers.trim().split(' ') {
-            if winning_number.is_empty() {
-                continue;
+            if 128 <= value {
+                return Err(line.error(number, "expected a number under 128"));
             }
 
-            let winning_number = winning_number.parse::<u8>().unwrap();
-            winners.insert(winning_number);
+            bits |= 1 << value;
         }
 
-        let mut number_found = 0;
-
-        for my_number in my_numbers.trim().split(' ') {
-            if my_number.is_empty() {
-                continue;
-            }
+        Ok(Numbers(bits))
+    }
 
-            let my_number = my_number.parse::<u8>().unwrap();
-            if winners.contains(&my_number) {
-                number_found += 1;
-            }
-        }
+    pub fn contains(self, number: u8) -> bool {
+        number < 128 && 0 != self.0 & (1 << number)
+    }
 
-        // 0 -> 0, 1 -> 1, 2 -> 2, 3 -> 4, 4 -> 8, etc.
-        let points = if 0 == number_found {
-            0
-        } else {
-            2_u32.pow(number_found - 1)
-        };
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
 
-        sol += points;
+    pub fn is_empty(self) -> bool {
+        0 == self.0
     }
 
-    Solution::U32(sol)
+    pub fn intersection(self, other: Self) -> Self {
+        Numbers(self.0 & other.0)
+    }
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let mut scratch_card_hits = HashMap::new();
+pub struct Card {
+    pub id: u32,
+    pub winning: Numbers,
+    pub mine: Numbers,
+}
 
-    for (scratch_card_id, line) in input.lines().enumerate() {
-        let mut winners = HashSet::new();
+impl Card {
+    pub fn parse(line: &Line) -> Result<Self, ParseError> {
+        let (id, numbers) = key_value(line, "Card")?;
+        let (winning, mine) = line.split_once(numbers, '|')?;
 
-        let (_, line) = line.split_once(':').unwrap();
-        let (winning_numbers, my_numbers) = line.split_once('|').unwrap();
+        Ok(Card {
+            id,
+            winning: Numbers::parse(line, winning)?,
+            mine: Numbers::parse(line, mine)?,
+        })
+    }
 
-        for winning_number in winning_numbers.trim().split(' ') {
-            if winning_number.is_empty() {
-                continue;
-            }
+    /// How many of our numbers are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning.intersection(self.mine).len()
+    }
 
-            let winning_number = winning_number.parse::<u8>().unwrap();
-            winners.insert(winning_number);
+    /// 0 -> 0, 1 -> 1, 2 -> 2, 3 -> 4, 4 -> 8, etc.
+    pub fn points(&self) -> u32 {
+        match self.matches() {
+            0 => 0,
+            matches => 1 << (matches - 1),
         }
+    }
+}
 
-        let mut number_found = 0;
+/// Every card, each parsed once and shared by both parts. The matches and copies of each card
+/// can be looked at on their own, not just summed up.
+pub struct Scratchcards {
+    pub cards: Vec<Card>,
+}
 
-        for my_number in my_numbers.trim().split(' ') {
-            if my_number.is_empty() {
-                continue;
-            }
+impl Scratchcards {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let cards = lines(input)
+            .map(|x| Card::parse(&x))
+            .collect::<Result<_, _>>()?;
 
-            let my_number = my_number.parse::<u8>().unwrap();
+        Ok(Scratchcards { cards })
+    }
 
-            if winners.contains(&my_number) {
-                number_found += 1;
-            }
-        }
+    /// How many of our numbers are winning numbers, for each card.
+    pub fn matches(&self) -> Vec<usize> {
+        self.cards.iter().map(Card::matches).collect()
+    }
 
-        scratch_card_hits.entry(scratch_card_id).or_insert(0);
+    /// How many of each card we end up with, once every card has won copies of the cards after
+    /// it. Copies that would go past the last card are dropped.
+    pub fn copies(&self) -> Vec<u32> {
+        let count = self.cards.len();
 
-        *scratch_card_hits.get_mut(&scratch_card_id).unwrap() += 1;
+        let mut copies = Vec::with_capacity(count);
 
-        if 0 == number_found {
-            continue;
-        }
+        // Rather than adding each card's copies to every card it wins, keep a running total of
+        // the copies being won and take each card's copies off it when they run out.
+        let mut winning = 0;
+        let mut expiring = vec![0; count + 1];
+
+        for (index, matches) in self.matches().into_iter().enumerate() {
+            winning -= expiring[index];
 
-        for index in 1..=number_found {
-            let id = scratch_card_id + index;
+            let card = 1 + winning;
 
-            scratch_card_hits.entry(id).or_insert(0);
+            winning += card;
+            expiring[(index + 1 + matches).min(count)] += card;
 
-            *scratch_card_hits.get_mut(&id).unwrap() +=
-                *scratch_card_hits.get(&scratch_card_id).unwrap();
+            copies.push(card);
         }
+
+        copies
+    }
+}
+
+impl Parsed for Scratchcards {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U32(self.cards.iter().map(Card::points).sum()))
     }
 
-    let sol = scratch_card_hits.iter().fold(0, |acc, (_, v)| acc + *v);
+    fn part2(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U32(self.copies().into_iter().sum()))
+    }
+}
 
-    Solution::U32(sol)
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    Scratchcards::parse(input)?.part1()
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day04"));
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    Scratchcards::parse(input)?.part2()
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    fn number(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Scratchcards::parse(input)?)))
+    }
 
-    (solve1(input), solve2(input))
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        line_problems(input, Card::parse)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day04-1");
+
     #[test]
     fn it_works() {
-        let input = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-        assert_eq!(super::solve1(input), Solution::U32(13));
-        assert_eq!(super::solve2(input), Solution::U32(30));
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(13)));
+        assert_eq!(super::solve2(input), Ok(Solution::U32(30)));
+
+        let error = super::solve2("Card 1: 41 48 | 83 4x").unwrap_err();
+
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 20, "4x")
+        );
+
+        let cards = super::Scratchcards::parse(input).unwrap();
+
+        assert_eq!(cards.matches(), [4, 2, 2, 1, 0, 0]);
+        assert_eq!(cards.copies(), [1, 2, 4, 8, 14, 1]);
+        assert_eq!(cards.cards[0].points(), 8);
+        assert!(cards.cards[2].winning.contains(1) && cards.cards[2].mine.contains(1));
+
+        // Cards can't win copies of cards past the end.
+        let cards = super::Scratchcards::parse("Card 1: 1 2 | 1 2\nCard 2: 3 | 3").unwrap();
+
+        assert_eq!(cards.copies(), [1, 2]);
+
+        let error = super::solve1("Card 1: 127 | 128").unwrap_err();
+
+        assert_eq!((error.column, error.text.as_str()), (15, "128"));
     }
 }
@@ -1,189 +1,384 @@
-use iset::IntervalMap;
 use itertools::*;
+use std::collections::HashMap;
+use std::ops::Range;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::{labelled_numbers, numbers, sections};
+use crate::etc::{Line, RangeMap};
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
 type IntTypeToUse = i64;
 
-pub fn solve1(input: &str) -> Solution {
-    let mut seeds = Vec::new();
+/// The seeds and the maps between each category of the almanac, like "seed" to "soil". The maps
+/// can be in any order. The whole chain from seed to location is collapsed into one map when
+/// parsing, so both parts are a lookup in that, and `map` gives the map between any other two
+/// categories.
+pub struct Almanac<'a> {
+    seeds_line: Line<'a>,
+    pub seeds: Vec<IntTypeToUse>,
+    /// Each map by its source category, along with its destination category.
+    maps: HashMap<&'a str, (&'a str, RangeMap)>,
+    /// The maps from seed to location collapsed into one.
+    locations: RangeMap,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let sections = sections(input);
+
+        let (seeds, sections) = sections
+            .split_first()
+            .ok_or_else(|| ParseError::at_end(input, "expected the seeds"))?;
+
+        if let Some(line) = seeds.get(1) {
+            return Err(line.error(line.text, "expected a blank line after the seeds"));
+        }
+
+        let seeds_line = seeds[0];
+        let seeds = labelled_numbers(&seeds_line, "seeds")?;
+
+        let mut maps = HashMap::new();
+        let mut headers = HashMap::new();
+
+        for section in sections {
+            // Sections are never empty.
+            let (header, lines) = section.split_first().unwrap();
+
+            let (source, destination) = parse_header(header)?;
+
+            if let Some(first) = headers.insert(source, header.index) {
+                return Err(duplicate_map(header, source, first));
+            }
 
-    let mut maps = Vec::new();
+            let mut ranges = Vec::new();
 
-    for line in input.lines() {
-        if let Some(line) = line.strip_prefix("seeds: ") {
-            for seed in line.split(' ') {
-                let seed = seed.parse::<IntTypeToUse>().unwrap();
-                seeds.push(seed);
+            for line in lines {
+                let (dst, src, len) = parse_range(line)?;
+
+                ranges.push((*line, src..(src + len), dst));
             }
-        } else if line.ends_with("map:") {
-            // We're just assuming everything is in order with the intervals!
-            maps.push(IntervalMap::new());
-        } else if !line.is_empty() {
-            let (dst, line) = line.split_once(' ').unwrap();
-            let (src, len) = line.split_once(' ').unwrap();
 
-            let dst = dst.parse::<IntTypeToUse>().unwrap();
-            let src = src.parse::<IntTypeToUse>().unwrap();
-            let len = len.parse::<IntTypeToUse>().unwrap();
+            // Each number can only go one place, so the source ranges can't overlap.
+            let sources = ranges.iter().map(|(line, src, _)| (*line, src.clone()));
+
+            if let Some(problem) = overlaps(sources.collect()).into_iter().next() {
+                return Err(problem);
+            }
 
-            let map = maps.last_mut().unwrap();
+            let map = RangeMap::new(ranges.into_iter().map(|(_, src, dst)| (src, dst)));
 
-            map.insert(src..(src + len), dst);
+            maps.insert(source, (destination, map));
         }
-    }
 
-    let mut sol = IntTypeToUse::MAX;
+        let destinations = maps.iter().map(|(k, (v, _))| (*k, *v)).collect();
 
-    for seed in seeds {
-        let mut id = seed;
+        if let Some(problem) = missing_map(&destinations) {
+            return Err(ParseError::at_end(input, problem));
+        }
 
-        for map in &maps {
-            let mut values = map.overlap(id);
+        let mut almanac = Almanac {
+            seeds_line,
+            seeds,
+            maps,
+            locations: RangeMap::identity(),
+        };
 
-            if let Some((range, dst)) = values.next() {
-                // We need to get the correct offset into dest, which means taking the base source range away from our current id (which could have been in the middle of the original range!).
-                let len = id - range.start;
+        almanac.locations = almanac.path("seed", "location").unwrap();
 
-                id = dst + len;
+        Ok(almanac)
+    }
+
+    /// The maps from `from` to `to` composed into one, if following the maps leads there.
+    fn path(&self, from: &str, to: &str) -> Option<RangeMap> {
+        let mut map = RangeMap::identity();
+        let mut category = from;
+
+        // A path can't use more maps than there are without going round in a loop.
+        for _ in 0..=self.maps.len() {
+            if to == category {
+                return Some(map);
             }
 
-            assert_eq!(0, values.count());
+            let (next, step) = self.maps.get(category)?;
 
-            // Otherwise id just maps to itself and we don't have to do anything.
+            map = map.then(step);
+            category = next;
         }
 
-        sol = sol.min(id);
+        None
+    }
+
+    /// The map from any category to any other, following the maps backwards if `to` comes before
+    /// `from`. Backwards only works if the maps never send two numbers to the same place.
+    pub fn map(&self, from: &str, to: &str) -> Option<RangeMap> {
+        match self.path(from, to) {
+            Some(map) => Some(map),
+            None => self.path(to, from)?.invert(),
+        }
     }
 
-    Solution::I64(sol)
+    /// The map from seed to location.
+    pub fn locations(&self) -> &RangeMap {
+        &self.locations
+    }
+
+    /// Part 2 reads the seeds as pairs of start and length.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<IntTypeToUse>>, ParseError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(unpaired_seeds(&self.seeds_line));
+        }
+
+        Ok(self
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(start, len)| *start..(start + len))
+            .collect())
+    }
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let mut ranges = Vec::new();
+impl Parsed for Almanac<'_> {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        let sol = self
+            .seeds
+            .iter()
+            .map(|x| self.locations.get(*x))
+            .fold(IntTypeToUse::MAX, IntTypeToUse::min);
 
-    let mut maps = Vec::new();
+        Ok(Solution::I64(sol))
+    }
 
-    for line in input.lines() {
-        if let Some(line) = line.strip_prefix("seeds: ") {
-            for (start, len) in line.split(' ').tuples() {
-                let start = start.parse::<IntTypeToUse>().unwrap();
-                let len = len.parse::<IntTypeToUse>().unwrap();
-                ranges.push(start..(start + len));
-            }
-        } else if line.ends_with("map:") {
-            // We're just assuming everything is in order with the intervals!
-            maps.push(IntervalMap::new());
-        } else if !line.is_empty() {
-            let (dst, line) = line.split_once(' ').unwrap();
-            let (src, len) = line.split_once(' ').unwrap();
+    fn part2(&self) -> Result<Solution, ParseError> {
+        let locations = self.locations.map_ranges(self.seed_ranges()?);
 
-            let dst = dst.parse::<IntTypeToUse>().unwrap();
-            let src = src.parse::<IntTypeToUse>().unwrap();
-            let len = len.parse::<IntTypeToUse>().unwrap();
+        let sol = locations.first().map_or(IntTypeToUse::MAX, |x| x.start);
 
-            let map = maps.last_mut().unwrap();
+        Ok(Solution::I64(sol))
+    }
+}
+
+/// The source and destination categories from an `X-to-Y map:` header.
+fn parse_header<'a>(header: &Line<'a>) -> Result<(&'a str, &'a str), ParseError> {
+    header
+        .text
+        .strip_suffix(" map:")
+        .and_then(|x| x.split_once("-to-"))
+        .ok_or_else(|| header.error(header.text, "expected an 'X-to-Y map:' header"))
+}
 
-            map.insert(src..(src + len), dst);
+fn duplicate_map(header: &Line, source: &str, first: usize) -> ParseError {
+    header.error(
+        header.text,
+        format!(
+            "expected one map from '{}' (already on line {})",
+            source,
+            first + 1
+        ),
+    )
+}
+
+/// What's missing, if following the maps from seed never gets to location.
+fn missing_map(destinations: &HashMap<&str, &str>) -> Option<String> {
+    let mut category = "seed";
+
+    for _ in 0..=destinations.len() {
+        if "location" == category {
+            return None;
+        }
+
+        match destinations.get(category) {
+            Some(next) => category = next,
+            None => {
+                return Some(format!(
+                    "expected a map from '{}' on the way to 'location'",
+                    category
+                ))
+            }
         }
     }
 
-    for map in &mut maps {
-        let mut ranges_missing = Vec::new();
+    Some("expected the maps from 'seed' to reach 'location' without a loop".to_string())
+}
 
-        let mut start = 0;
+/// Part 2 reads the seeds as pairs of start and length.
+fn unpaired_seeds(line: &Line) -> ParseError {
+    line.error(line.text, "expected the seeds in pairs of start and length")
+}
 
-        for (range, _) in map.iter(0..IntTypeToUse::MAX) {
-            if start < range.start {
-                // Remember the missing range.
-                ranges_missing.push(start..range.start);
-            }
+/// The destination start, source start and length from a line of a map.
+fn parse_range(line: &Line) -> Result<(IntTypeToUse, IntTypeToUse, IntTypeToUse), ParseError> {
+    match numbers::<IntTypeToUse>(line, line.text)?[..] {
+        [dst, src, len] if 0 < len => Ok((dst, src, len)),
+        [_, _, _] => Err(line.error(line.text, "expected a length of at least one")),
+        _ => Err(line.error(line.text, "expected a destination, source and length")),
+    }
+}
 
-            // And bump over to the end of the known recorded range.
-            start = range.end;
+/// A problem for each line whose source range overlaps the range of another line in its map.
+fn overlaps(mut ranges: Vec<(Line, Range<IntTypeToUse>)>) -> Vec<ParseError> {
+    ranges.sort_by_key(|(_, range)| range.start);
+
+    let mut problems = Vec::new();
+
+    for ((previous, a), (line, b)) in ranges.iter().tuple_windows() {
+        if b.start < a.end {
+            problems.push(line.error(
+                line.text,
+                format!(
+                    "expected a range that doesn't overlap line {}",
+                    previous.index + 1
+                ),
+            ));
         }
+    }
+
+    problems
+}
 
-        ranges_missing.push(start..IntTypeToUse::MAX);
+/// Every problem with the almanac: the seeds, the map headers (which have to lead from seeds to
+/// locations, in any order), and the ranges of each map.
+fn validate(input: &str) -> Vec<ParseError> {
+    let sections = sections(input);
 
-        for range in ranges_missing {
-            let start = range.start;
-            map.insert(range, start);
-        }
+    let Some((seeds, maps)) = sections.split_first() else {
+        return vec![ParseError::at_end(input, "expected the seeds")];
+    };
+
+    let mut problems = Vec::new();
+
+    if let Some(line) = seeds.get(1) {
+        problems.push(line.error(line.text, "expected a blank line after the seeds"));
     }
 
-    for map in &maps {
-        let ranges_copy = ranges;
+    match labelled_numbers::<IntTypeToUse>(&seeds[0], "seeds") {
+        Ok(numbers) if !numbers.len().is_multiple_of(2) => problems.push(unpaired_seeds(&seeds[0])),
+        Ok(_) => (),
+        Err(problem) => problems.push(problem),
+    }
 
-        // Wipe out ranges so we can record the next loop iteration!
-        ranges = Vec::new();
+    let mut destinations = HashMap::new();
+    let mut headers = HashMap::new();
 
-        for in_range in ranges_copy {
-            for (range, to_dst) in map.iter(in_range.clone()) {
-                let offset = to_dst - range.start;
+    for section in maps {
+        // Sections are never empty.
+        let (header, lines) = section.split_first().unwrap();
 
-                let clamped_range = range.start.max(in_range.start)..range.end.min(in_range.end);
+        match parse_header(header) {
+            Ok((source, destination)) => {
+                if let Some(first) = headers.insert(source, header.index) {
+                    problems.push(duplicate_map(header, source, first));
+                }
 
-                ranges.push((clamped_range.start + offset)..(clamped_range.end + offset));
+                destinations.insert(source, destination);
             }
+            Err(problem) => problems.push(problem),
         }
+
+        let mut ranges = Vec::new();
+
+        for line in lines {
+            match parse_range(line) {
+                Ok((_, src, len)) => ranges.push((*line, src..(src + len))),
+                Err(problem) => problems.push(problem),
+            }
+        }
+
+        problems.extend(overlaps(ranges));
     }
 
-    let sol = ranges
-        .iter()
-        .fold(IntTypeToUse::MAX, |acc, x| acc.min(x.start));
+    if let Some(problem) = missing_map(&destinations) {
+        problems.push(ParseError::at_end(input, problem));
+    }
+
+    problems.sort_by_key(|x| (x.line, x.column));
+    problems
+}
+
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    Almanac::parse(input)?.part1()
+}
 
-    Solution::I64(sol)
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    Almanac::parse(input)?.part2()
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day05"));
+pub struct Day05;
+
+impl Day for Day05 {
+    fn number(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
 
-    (solve1(input), solve2(input))
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Almanac::parse(input)?)))
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        validate(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day05-1");
+
     #[test]
     fn it_works() {
-        let input = r"seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-
-        assert_eq!(super::solve1(input), Solution::I64(35));
-        assert_eq!(super::solve2(input), Solution::I64(46));
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::I64(35)));
+        assert_eq!(super::solve2(input), Ok(Solution::I64(46)));
+
+        let error = super::solve1("seeds: 79\n\n50 98 2").unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 1));
+
+        assert!(super::validate(input).is_empty());
+
+        let problems = super::validate(
+            "seeds: 1 2 3\n\nseed-to-soil map:\n0 5 3\n9 6 1\n\nwater-to-location map:\n0 x 1",
+        );
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(1, 1), (5, 1), (8, 3), (9, 1)]
+        );
+
+        let almanac = super::Almanac::parse(input).unwrap();
+
+        assert_eq!(almanac.locations().get(79), 82);
+        assert_eq!(almanac.map("soil", "fertilizer").unwrap().get(81), 81);
+        assert_eq!(almanac.map("seed", "water").unwrap().get(14), 49);
+        assert_eq!(almanac.map("location", "seed").unwrap().get(82), 79);
+        assert_eq!(almanac.map("soil", "soil"), Some(etc::RangeMap::identity()));
+
+        // The maps can come in any order.
+        let mut sections: Vec<_> = input.trim_end().split("\n\n").collect();
+
+        sections[1..].reverse();
+
+        let reversed = sections.join("\n\n");
+
+        assert_eq!(super::solve1(&reversed), Ok(Solution::I64(35)));
+        assert_eq!(super::solve2(&reversed), Ok(Solution::I64(46)));
+        assert!(super::validate(&reversed).is_empty());
     }
 }
@@ -1,24 +1,83 @@
-use itertools::*;
-
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::{numbers, strip_label};
+use crate::etc::{lines, Line};
+use crate::{Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let (time, distance) = input.lines().tuples().next().unwrap();
+/// A line along with what follows its label.
+type Labelled<'a> = (Line<'a>, &'a str);
 
-    let time = time.strip_prefix("Time:").unwrap();
-    let distance = distance.strip_prefix("Distance:").unwrap();
+/// The next line, which should start with `label:`.
+fn labelled<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    input: &str,
+    label: &str,
+) -> Result<Labelled<'a>, ParseError> {
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, format!("expected a '{}:' line", label)))?;
 
-    let time = time.split(' ').filter(|x| !x.is_empty());
-    let distance = distance.split(' ').filter(|x| !x.is_empty());
+    Ok((line, strip_label(&line, label)?))
+}
 
-    let mut sol = 1;
+fn parse_lines(input: &str) -> Result<(Labelled<'_>, Labelled<'_>), ParseError> {
+    let mut lines = lines(input);
+
+    Ok((
+        labelled(&mut lines, input, "Time")?,
+        labelled(&mut lines, input, "Distance")?,
+    ))
+}
+
+/// Every bad label and number on both lines, and whether there are as many distances as times.
+fn validate(input: &str) -> Vec<ParseError> {
+    let mut lines = lines(input);
+    let mut problems = Vec::new();
+    let mut counts = Vec::new();
+
+    for label in ["Time", "Distance"] {
+        match labelled(&mut lines, input, label) {
+            Ok((line, text)) => {
+                problems.extend(
+                    text.split_whitespace()
+                        .filter_map(|x| line.number::<u32>(x).err()),
+                );
+
+                counts.push((line, text.split_whitespace().count()));
+            }
+            Err(problem) => problems.push(problem),
+        }
+    }
 
-    for (time, distance) in time.zip_eq(distance) {
-        let time = time.parse::<u32>().unwrap();
-        let distance = distance.parse::<u32>().unwrap();
+    if let [(_, times), (line, distances)] = counts[..] {
+        if times != distances {
+            problems.push(mismatched(&line, times));
+        }
+    }
 
+    problems
+}
+
+fn mismatched(distance_line: &Line, times: usize) -> ParseError {
+    distance_line.error(
+        distance_line.text,
+        format!("expected {} distances to match the times", times),
+    )
+}
+
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    let ((time_line, time), (distance_line, distance)) = parse_lines(input)?;
+
+    let time: Vec<u32> = numbers(&time_line, time)?;
+    let distance: Vec<u32> = numbers(&distance_line, distance)?;
+
+    if time.len() != distance.len() {
+        return Err(mismatched(&distance_line, time.len()));
+    }
+
+    let mut sol = 1;
+
+    for (time, distance) in time.into_iter().zip(distance) {
         let mut hits = 0;
 
         for t in 0..time {
@@ -32,11 +91,11 @@ pub fn solve1(input: &str) -> Solution {
         sol *= hits;
     }
 
-    Solution::I32(sol)
+    Ok(Solution::I32(sol))
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let (time, distance) = input.lines().tuples().next().unwrap();
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    let ((_, time), (_, distance)) = parse_lines(input)?;
 
     let time = time
         .chars()
@@ -57,25 +116,61 @@ pub fn solve2(input: &str) -> Solution {
         }
     }
 
-    Solution::I32(hits)
+    Ok(Solution::I32(hits))
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day06"));
+pub struct Day06;
+
+impl Day for Day06 {
+    fn number(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
 
-    (solve1(input), solve2(input))
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        validate(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day06-1");
+
     #[test]
     fn it_works() {
-        let input = r"Time:      7  15   30
-Distance:  9  40  200";
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::I32(288)));
+        assert_eq!(super::solve2(input), Ok(Solution::I32(71503)));
+
+        assert_eq!(super::solve1("Time: 7").unwrap_err().line, 2);
+        assert_eq!(
+            super::solve1("Time: 7 15\nDistance: 9").unwrap_err().line,
+            2
+        );
+
+        assert!(super::validate(input).is_empty());
+
+        let problems = super::validate("Time: 7 x\nDistance: y 9");
 
-        assert_eq!(super::solve1(input), Solution::I32(288));
-        assert_eq!(super::solve2(input), Solution::I32(71503));
+        assert_eq!(
+            problems.iter().map(|x| x.text.as_str()).collect::<Vec<_>>(),
+            ["x", "y"]
+        );
+        assert_eq!(super::validate("Time: 7 15\nDist: 9").len(), 1);
+        assert_eq!(super::validate("Time: 7 15\nDistance: 9")[0].line, 2);
     }
 }
@@ -3,7 +3,9 @@ use std::cmp::Ordering;
 use strum::EnumCount;
 use strum_macros::EnumCount;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::line_problems;
+use crate::etc::{lines, Line};
+use crate::{Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
@@ -24,9 +26,11 @@ enum Card1 {
     Two,
 }
 
-impl From<char> for Card1 {
-    fn from(c: char) -> Self {
-        match c {
+impl TryFrom<char> for Card1 {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, ()> {
+        Ok(match c {
             'A' => Self::Ace,
             'K' => Self::King,
             'Q' => Self::Queen,
@@ -40,11 +44,28 @@ impl From<char> for Card1 {
             '4' => Self::Four,
             '3' => Self::Three,
             '2' => Self::Two,
-            _ => unreachable!(),
-        }
+            _ => return Err(()),
+        })
     }
 }
 
+/// The five cards and the bid from a line of the input.
+fn parse_hand<C: TryFrom<char>>(line: &Line) -> Result<([C; 5], u32), ParseError> {
+    let (hand, bid) = line.split_once(line.text, ' ')?;
+
+    let cards = hand
+        .char_indices()
+        .map(|(index, c)| {
+            C::try_from(c)
+                .map_err(|_| line.error(&hand[index..(index + c.len_utf8())], "expected a card"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cards = <[C; 5]>::try_from(cards).map_err(|_| line.error(hand, "expected five cards"))?;
+
+    Ok((cards, line.number(bid)?))
+}
+
 #[derive(Eq, PartialEq, Debug)]
 struct Hand1 {
     bid: u32,
@@ -52,19 +73,6 @@ struct Hand1 {
 }
 
 impl Hand1 {
-    pub fn new(bid: u32, hand: &str) -> Hand1 {
-        let mut hand_array = [Card1::Ace; 5];
-
-        for (index, c) in hand.chars().enumerate() {
-            hand_array[index] = Card1::from(c);
-        }
-
-        Hand1 {
-            bid,
-            hand: hand_array,
-        }
-    }
-
     fn rank(&self) -> usize {
         let mut hits = [0; Card1::COUNT];
 
@@ -109,42 +117,40 @@ impl Hand1 {
 
 impl Ord for Hand1 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl PartialOrd for Hand1 {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         let my_rank = self.rank();
         let other_rank = other.rank();
 
         match my_rank.cmp(&other_rank) {
-            Ordering::Greater => Some(Ordering::Less),
-            Ordering::Less => Some(Ordering::Greater),
+            Ordering::Greater => Ordering::Less,
+            Ordering::Less => Ordering::Greater,
             Ordering::Equal => {
                 for (m, o) in self.hand.iter().zip_eq(other.hand.iter()) {
                     match m.cmp(o) {
-                        Ordering::Greater => return Some(std::cmp::Ordering::Less),
-                        Ordering::Less => return Some(std::cmp::Ordering::Greater),
+                        Ordering::Greater => return Ordering::Less,
+                        Ordering::Less => return Ordering::Greater,
                         Ordering::Equal => (),
                     }
                 }
 
-                unreachable!()
+                Ordering::Equal
             }
         }
     }
 }
 
-pub fn solve1(input: &str) -> Solution {
+impl PartialOrd for Hand1 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
     let mut hands = Vec::new();
 
-    for line in input.lines() {
-        let (hand, bid) = line.split_once(' ').unwrap();
-        assert_eq!(5, hand.len());
-        let bid = bid.parse::<u32>().unwrap();
+    for line in lines(input) {
+        let (hand, bid) = parse_hand(&line)?;
 
-        hands.push(Hand1::new(bid, hand));
+        hands.push(Hand1 { bid, hand });
     }
 
     hands.sort();
@@ -155,7 +161,7 @@ pub fn solve1(input: &str) -> Solution {
         sol += ((index as u32) + 1) * hand.bid;
     }
 
-    Solution::U32(sol)
+    Ok(Solution::U32(sol))
 }
 
 #[derive(Clone, Copy, Debug, EnumCount, PartialEq, PartialOrd, Ord, Eq)]
@@ -175,9 +181,11 @@ enum Card2 {
     Joker,
 }
 
-impl From<char> for Card2 {
-    fn from(c: char) -> Self {
-        match c {
+impl TryFrom<char> for Card2 {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, ()> {
+        Ok(match c {
             'A' => Self::Ace,
             'K' => Self::King,
             'Q' => Self::Queen,
@@ -191,8 +199,8 @@ impl From<char> for Card2 {
             '3' => Self::Three,
             '2' => Self::Two,
             'J' => Self::Joker,
-            _ => unreachable!(),
-        }
+            _ => return Err(()),
+        })
     }
 }
 
@@ -203,19 +211,6 @@ struct Hand2 {
 }
 
 impl Hand2 {
-    pub fn new(bid: u32, hand: &str) -> Hand2 {
-        let mut hand_array = [Card2::Ace; 5];
-
-        for (index, c) in hand.chars().enumerate() {
-            hand_array[index] = Card2::from(c);
-        }
-
-        Hand2 {
-            bid,
-            hand: hand_array,
-        }
-    }
-
     fn rank(&self) -> usize {
         let mut hits = [0; Card2::COUNT];
 
@@ -268,42 +263,40 @@ impl Hand2 {
 
 impl Ord for Hand2 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl PartialOrd for Hand2 {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         let my_rank = self.rank();
         let other_rank = other.rank();
 
         match my_rank.cmp(&other_rank) {
-            Ordering::Greater => Some(Ordering::Less),
-            Ordering::Less => Some(Ordering::Greater),
+            Ordering::Greater => Ordering::Less,
+            Ordering::Less => Ordering::Greater,
             Ordering::Equal => {
                 for (m, o) in self.hand.iter().zip_eq(other.hand.iter()) {
                     match m.cmp(o) {
-                        Ordering::Greater => return Some(std::cmp::Ordering::Less),
-                        Ordering::Less => return Some(std::cmp::Ordering::Greater),
+                        Ordering::Greater => return Ordering::Less,
+                        Ordering::Less => return Ordering::Greater,
                         Ordering::Equal => (),
                     }
                 }
 
-                unreachable!()
+                Ordering::Equal
             }
         }
     }
 }
 
-pub fn solve2(input: &str) -> Solution {
+impl PartialOrd for Hand2 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
     let mut hands = Vec::new();
 
-    for line in input.lines() {
-        let (hand, bid) = line.split_once(' ').unwrap();
-        assert_eq!(5, hand.len());
-        let bid = bid.parse::<u32>().unwrap();
+    for line in lines(input) {
+        let (hand, bid) = parse_hand(&line)?;
 
-        hands.push(Hand2::new(bid, hand));
+        hands.push(Hand2 { bid, hand });
     }
 
     hands.sort();
@@ -314,28 +307,49 @@ pub fn solve2(input: &str) -> Solution {
         sol += ((index as u32) + 1) * hand.bid;
     }
 
-    Solution::U32(sol)
+    Ok(Solution::U32(sol))
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day07"));
+pub struct Day07;
 
-    (solve1(input), solve2(input))
+impl Day for Day07 {
+    fn number(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        line_problems(input, parse_hand::<Card1>)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day07-1");
+
     #[test]
     fn it_works() {
-        let input = r"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
-        assert_eq!(super::solve1(input), Solution::U32(6440));
-        assert_eq!(super::solve2(input), Solution::U32(5905));
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(6440)));
+        assert_eq!(super::solve2(input), Ok(Solution::U32(5905)));
+
+        let error = super::solve2("32T3K 765\nT55X5 684").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 4, "X"));
+        assert_eq!(super::solve1("32T3 765").unwrap_err().text, "32T3");
     }
 }
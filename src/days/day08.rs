@@ -1,157 +1,235 @@
 use std::collections::HashMap;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::lines;
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let instructions = input.lines().next().unwrap();
+struct Network<'a> {
+    input: &'a str,
+    instructions: &'a str,
+    map: HashMap<&'a str, (&'a str, &'a str)>,
+}
 
-    let mut map = HashMap::new();
+impl<'a> Network<'a> {
+    fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let (network, problems) = Network::read(input);
 
-    for line in input.lines().skip(1) {
-        if line.is_empty() {
-            continue;
+        match problems.into_iter().next() {
+            Some(problem) => Err(problem),
+            None => Ok(network),
         }
+    }
 
-        let (node, line) = line.split_once('=').unwrap();
-        let node = node.trim();
+    /// The network along with every problem with the input, in the order they appear. The
+    /// network can only be walked when there are no problems.
+    fn read(input: &'a str) -> (Self, Vec<ParseError>) {
+        let mut problems = Vec::new();
+        let mut lines = lines(input);
 
-        let (left, right) = line.split_once(',').unwrap();
-        let left = left.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
-        let right = right.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
+        let instructions = match lines.next() {
+            Some(first) => {
+                if first.text.is_empty() {
+                    problems.push(first.error(first.text, "expected at least one instruction"));
+                }
 
-        assert_eq!(3, node.len());
-        assert_eq!(3, left.len());
-        assert_eq!(3, right.len());
+                for (index, _) in first.text.match_indices(|x| !matches!(x, 'L' | 'R')) {
+                    problems.push(first.error_at(index, "expected 'L' or 'R'"));
+                }
 
-        map.insert(node, (left, right));
-    }
+                first.text
+            }
+            None => {
+                problems.push(ParseError::at_end(input, "expected the instructions"));
+                ""
+            }
+        };
+
+        let mut map = HashMap::new();
+        let mut defined_on = HashMap::new();
+        let mut targets = Vec::new();
 
-    let mut current = "AAA";
-    let mut index = 0;
-    let mut steps = 0;
+        for line in lines {
+            if line.text.is_empty() {
+                continue;
+            }
 
-    while "ZZZ" != current {
-        let instruction = instructions.chars().nth(index).unwrap();
+            let node = || -> Result<_, ParseError> {
+                let (node, rest) = line.split_once(line.text, '=')?;
+                let node = node.trim();
 
-        match instruction {
-            'L' => current = map[current].0,
-            'R' => current = map[current].1,
-            _ => unreachable!(),
+                let (left, right) = line.split_once(rest, ',')?;
+                let left = left.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
+                let right = right.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
+
+                for name in [node, left, right] {
+                    if 3 != name.len() {
+                        return Err(line.error(name, "expected a three letter node"));
+                    }
+                }
+
+                Ok((node, left, right))
+            };
+
+            let (node, left, right) = match node() {
+                Ok(node) => node,
+                Err(problem) => {
+                    problems.push(problem);
+                    continue;
+                }
+            };
+
+            if let Some(first) = defined_on.insert(node, line.index) {
+                problems.push(line.error(
+                    node,
+                    format!("expected each node once (already on line {})", first + 1),
+                ));
+            }
+
+            map.insert(node, (left, right));
+            targets.extend([(line, left), (line, right)]);
         }
 
-        index += 1;
-        index %= instructions.len();
+        // Check every node we could walk to is there, so that walking never has to.
+        for (line, target) in targets {
+            if !map.contains_key(target) {
+                problems.push(line.error(target, "expected a node that is in the network"));
+            }
+        }
+
+        problems.sort_by_key(|x| (x.line, x.column));
+
+        let network = Network {
+            input,
+            instructions,
+            map,
+        };
 
-        steps += 1;
+        (network, problems)
     }
 
-    Solution::U32(steps)
-}
+    fn steps(&self, start: &'a str, is_end: impl Fn(&str) -> bool) -> u64 {
+        let mut current = start;
+        let mut index = 0;
+        let mut steps = 0;
+
+        let instructions = self.instructions.as_bytes();
 
-pub fn solve2(input: &str) -> Solution {
-    let instructions = input.lines().next().unwrap();
+        while !is_end(current) {
+            if b'L' == instructions[index] {
+                current = self.map[current].0;
+            } else {
+                current = self.map[current].1;
+            }
 
-    let mut current = Vec::new();
-    let mut map = HashMap::new();
+            index += 1;
+            index %= instructions.len();
 
-    for line in input.lines().skip(1) {
-        if line.is_empty() {
-            continue;
+            steps += 1;
         }
 
-        let (node, line) = line.split_once('=').unwrap();
-        let node = node.trim();
+        steps
+    }
+}
 
-        if node.ends_with('A') {
-            current.push(node);
+impl Parsed for Network<'_> {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        if !self.map.contains_key("AAA") {
+            return Err(ParseError::at_end(
+                self.input,
+                "expected a node called 'AAA'",
+            ));
         }
 
-        let (left, right) = line.split_once(',').unwrap();
-        let left = left.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
-        let right = right.trim_matches(|x| matches!(x, '(' | ')' | ',' | ' '));
+        Ok(Solution::U32(self.steps("AAA", |x| "ZZZ" == x) as u32))
+    }
 
-        assert_eq!(3, node.len());
-        assert_eq!(3, left.len());
-        assert_eq!(3, right.len());
+    fn part2(&self) -> Result<Solution, ParseError> {
+        let sol = self
+            .map
+            .keys()
+            .filter(|x| x.ends_with('A'))
+            .map(|x| self.steps(x, |x| x.ends_with('Z')))
+            .fold(1, num::integer::lcm);
 
-        map.insert(node, (left, right));
+        Ok(Solution::U64(sol))
     }
+}
 
-    let sol = current
-        .iter()
-        .map(|x| {
-            let mut x = *x;
-
-            let mut index = 0;
-            let mut steps = 0;
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    Network::parse(input)?.part1()
+}
 
-            while !x.ends_with('Z') {
-                let instruction = instructions.chars().nth(index).unwrap();
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    Network::parse(input)?.part2()
+}
 
-                match instruction {
-                    'L' => x = map[x].0,
-                    'R' => x = map[x].1,
-                    _ => unreachable!(),
-                }
+pub struct Day08;
 
-                index += 1;
-                index %= instructions.len();
+impl Day for Day08 {
+    fn number(&self) -> u8 {
+        8
+    }
 
-                steps += 1;
-            }
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
 
-            steps as u64
-        })
-        .fold(1, num::integer::lcm);
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
 
-    Solution::U64(sol)
-}
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day08"));
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Network::parse(input)?)))
+    }
 
-    (solve1(input), solve2(input))
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        Network::read(input).1
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE1: &str = include_str!("../../examples/day08-1");
+    const EXAMPLE2: &str = include_str!("../../examples/day08-2");
+    const EXAMPLE3: &str = include_str!("../../examples/day08-3");
+
     #[test]
     fn it_works() {
-        let input = r"RL
+        let input = EXAMPLE1;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(2)));
 
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
+        let input = EXAMPLE2;
 
-        assert_eq!(super::solve1(input), Solution::U32(2));
+        assert_eq!(super::solve1(input), Ok(Solution::U32(6)));
 
-        let input = r"LLR
+        let input = EXAMPLE3;
 
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(super::solve2(input), Ok(Solution::U64(6)));
 
-        assert_eq!(super::solve1(input), Solution::U32(6));
+        let error = super::solve1("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
 
-        let input = r"LR
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 8, "BBB")
+        );
+        assert_eq!(super::solve1("LX\n").unwrap_err().column, 2);
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
+        let (_, problems) = super::Network::read("LXR\n\nAAA = (BBB, AAA)\nAAA = (AAA, AAA)\nBB");
 
-        assert_eq!(super::solve2(input), Solution::U64(6));
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(1, 2), (3, 8), (4, 1), (5, 1)]
+        );
     }
 }
@@ -1,23 +1,42 @@
 use itertools::*;
 use num::Zero;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::parse::{line_problems, numbers};
+use crate::etc::{lines, Line};
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve1(input: &str) -> Solution {
-    let mut sol = 0;
+/// The numbers of a history, which needs at least two to have a difference to extrapolate.
+fn parse_history(line: &Line) -> Result<Vec<i32>, ParseError> {
+    let history = numbers(line, line.text)?;
 
-    for line in input.lines() {
-        let mut sequences = Vec::new();
+    if history.len() < 2 {
+        return Err(line.error(line.text, "expected at least two numbers"));
+    }
+
+    Ok(history)
+}
 
-        sequences.push(Vec::new());
+struct Report {
+    histories: Vec<Vec<i32>>,
+}
+
+impl Report {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut histories = Vec::new();
 
-        for element in line.split(' ') {
-            let element = element.parse::<i32>().unwrap();
-            sequences[0].push(element);
+        for line in lines(input) {
+            histories.push(parse_history(&line)?);
         }
 
+        Ok(Report { histories })
+    }
+
+    // Keep taking differences until the sequence is all zeros.
+    fn sequences(history: &[i32]) -> Vec<Vec<i32>> {
+        let mut sequences = vec![history.to_vec()];
+
         while !sequences.last().unwrap().iter().all(i32::is_zero) {
             let mut sequence = Vec::new();
 
@@ -28,74 +47,122 @@ pub fn solve1(input: &str) -> Solution {
             sequences.push(sequence);
         }
 
-        let mut diff = 0;
+        sequences
+    }
+
+    fn extrapolate_forwards(&self) -> i32 {
+        let mut sol = 0;
+
+        for history in &self.histories {
+            let mut diff = 0;
 
-        for sequence in sequences.iter().rev() {
-            let last = sequence.last().unwrap();
+            for sequence in Self::sequences(history).iter().rev() {
+                // A history that runs out of differences before they're all zero ends with an
+                // empty one, which doesn't add anything.
+                diff += sequence.last().unwrap_or(&0);
+            }
 
-            diff += last;
+            sol += diff;
         }
 
-        sol += diff;
+        sol
     }
 
-    Solution::I32(sol)
-}
-
-pub fn solve2(input: &str) -> Solution {
-    let mut sol = 0;
+    fn extrapolate_backwards(&self) -> i32 {
+        let mut sol = 0;
 
-    for line in input.lines() {
-        let mut sequences = Vec::new();
+        for history in &self.histories {
+            let mut diff = 0;
 
-        sequences.push(Vec::new());
+            for sequence in Self::sequences(history).iter().rev() {
+                diff = sequence.first().unwrap_or(&0) - diff;
+            }
 
-        for element in line.split(' ') {
-            let element = element.parse::<i32>().unwrap();
-            sequences[0].push(element);
+            sol += diff;
         }
 
-        while !sequences.last().unwrap().iter().all(i32::is_zero) {
-            let mut sequence = Vec::new();
+        sol
+    }
+}
 
-            for (e, n) in sequences.last().unwrap().iter().tuple_windows() {
-                sequence.push(n - e);
-            }
+impl Parsed for Report {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::I32(self.extrapolate_forwards()))
+    }
 
-            sequences.push(sequence);
-        }
+    fn part2(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::I32(self.extrapolate_backwards()))
+    }
+}
 
-        let mut diff = 0;
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    Report::parse(input)?.part1()
+}
 
-        for sequence in sequences.iter().rev() {
-            let first = sequence.first().unwrap();
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    Report::parse(input)?.part2()
+}
 
-            diff = first - diff;
-        }
+pub struct Day09;
 
-        sol += diff;
+impl Day for Day09 {
+    fn number(&self) -> u8 {
+        9
     }
 
-    Solution::I32(sol)
-}
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day09"));
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        line_problems(input, parse_history)
+    }
 
-    (solve1(input), solve2(input))
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Report::parse(input)?)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::Day09;
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day09-1");
+
     #[test]
     fn it_works() {
-        let input = r"0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::I32(114)));
+        assert_eq!(super::solve2(input), Ok(Solution::I32(2)));
+
+        let error = super::solve1("0 3 6\n1 3 x 10").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 5));
+
+        let error = super::solve1("0 3 6\n\n1 2 3").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(super::solve2("5").unwrap_err().text, "5");
+        assert_eq!(
+            Day09
+                .validate("0 3 6\n5\n\n1 x 3")
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(2, 1), (3, 1), (4, 3)]
+        );
 
-        assert_eq!(super::solve1(input), Solution::I32(114));
-        assert_eq!(super::solve2(input), Solution::I32(2));
+        // 1 8 27 runs out at the single difference 12, which isn't zero.
+        assert_eq!(super::solve1("1 8 27"), Ok(Solution::I32(58)));
     }
 }
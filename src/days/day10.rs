@@ -1,15 +1,8 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::{lines, Direction, Grid, Point};
+use crate::{Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Debug)]
 enum Pipe {
     Start,
@@ -23,285 +16,228 @@ enum Pipe {
 }
 
 impl Pipe {
-    fn is_up(&self) -> bool {
-        matches!(self, Pipe::UpAndDown | Pipe::LeftAndUp | Pipe::RightAndUp)
+    /// Whether the pipe has an opening on its `side`.
+    fn connects(self, side: Direction) -> bool {
+        match side {
+            Direction::Up => matches!(self, Pipe::UpAndDown | Pipe::LeftAndUp | Pipe::RightAndUp),
+            Direction::Down => matches!(
+                self,
+                Pipe::UpAndDown | Pipe::LeftAndDown | Pipe::RightAndDown
+            ),
+            Direction::Left => matches!(
+                self,
+                Pipe::LeftAndRight | Pipe::LeftAndUp | Pipe::LeftAndDown
+            ),
+            Direction::Right => matches!(
+                self,
+                Pipe::LeftAndRight | Pipe::RightAndUp | Pipe::RightAndDown
+            ),
+        }
     }
+}
 
-    fn is_down(&self) -> bool {
-        matches!(
-            self,
-            Pipe::UpAndDown | Pipe::LeftAndDown | Pipe::RightAndDown
-        )
+const SYMBOLS: [(char, Pipe); 8] = [
+    ('-', Pipe::LeftAndRight),
+    ('|', Pipe::UpAndDown),
+    ('L', Pipe::RightAndUp),
+    ('J', Pipe::LeftAndUp),
+    ('7', Pipe::LeftAndDown),
+    ('F', Pipe::RightAndDown),
+    ('S', Pipe::Start),
+    ('.', Pipe::None),
+];
+
+impl TryFrom<char> for Pipe {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, ()> {
+        SYMBOLS
+            .iter()
+            .find(|(c, _)| *c == value)
+            .map(|(_, pipe)| *pipe)
+            .ok_or(())
     }
+}
 
-    fn is_left(&self) -> bool {
-        matches!(
-            self,
-            Pipe::LeftAndRight | Pipe::LeftAndUp | Pipe::LeftAndDown
-        )
+impl Pipe {
+    fn symbol(self) -> char {
+        SYMBOLS.iter().find(|(_, pipe)| *pipe == self).unwrap().0
     }
+}
 
-    fn is_right(&self) -> bool {
-        matches!(
-            self,
-            Pipe::LeftAndRight | Pipe::RightAndUp | Pipe::RightAndDown
-        )
-    }
+/// Where we are on the loop, and the direction we were heading when we got there.
+type Position = (Direction, Point<usize>);
+
+fn starts(board: &Grid<Pipe>) -> Vec<Point<usize>> {
+    board
+        .iter()
+        .filter(|(_, pipe)| Pipe::Start == **pipe)
+        .map(|(point, _)| point)
+        .collect()
 }
 
-impl From<char> for Pipe {
-    fn from(value: char) -> Self {
-        match value {
-            '-' => Self::LeftAndRight,
-            '|' => Self::UpAndDown,
-            'L' => Self::RightAndUp,
-            'J' => Self::LeftAndUp,
-            '7' => Self::LeftAndDown,
-            'F' => Self::RightAndDown,
-            'S' => Self::Start,
-            '.' => Self::None,
-            x => panic!("{}", x),
-        }
+/// The board and the position of its only start.
+fn parse_board(input: &str) -> Result<(Grid<Pipe>, Point<usize>), ParseError> {
+    let board = Grid::parse(input, |c| Pipe::try_from(c).ok())?;
+
+    let starts = starts(&board);
+
+    match starts[..] {
+        [start] => Ok((board, start)),
+        [] => Err(ParseError::at_end(input, "expected a start ('S')")),
+        [_, other, ..] => Err(error_at(&board, other, "expected only one start")),
     }
 }
 
-pub fn solve1(input: &str) -> Solution {
-    let mut board = Vec::new();
-    let mut width = None;
-    let mut start = None;
+fn error_at(board: &Grid<Pipe>, point: Point<usize>, message: &str) -> ParseError {
+    ParseError::new(
+        point.y + 1,
+        point.x + 1,
+        &board[point].symbol().to_string(),
+        message,
+    )
+}
 
-    for line in input.lines() {
-        if let Some(width) = width {
-            assert_eq!(width, line.len());
-        } else {
-            width = Some(line.len());
+/// Every bad character, row of the wrong width and extra start, or else whatever is wrong with
+/// the pipes around the start.
+fn validate(input: &str) -> Vec<ParseError> {
+    let Ok(board) = Grid::parse(input, |c| Pipe::try_from(c).ok()) else {
+        let mut problems = Grid::<Pipe>::problems(input, |c| Pipe::try_from(c).is_ok());
+
+        // Without a board, find the starts in the text instead.
+        let starts: Vec<_> = lines(input)
+            .flat_map(|line| {
+                line.text
+                    .match_indices('S')
+                    .map(move |(index, _)| (line, index))
+            })
+            .collect();
+
+        match starts[..] {
+            [] => problems.push(ParseError::at_end(input, "expected a start ('S')")),
+            [_, ref others @ ..] => problems.extend(
+                others
+                    .iter()
+                    .map(|(line, index)| line.error_at(*index, "expected only one start")),
+            ),
         }
 
-        for c in line.chars() {
-            let pipe = Pipe::from(c);
+        problems.sort_by_key(|x| (x.line, x.column));
 
-            if Pipe::Start == pipe {
-                assert!(start.is_none());
-                start = Some(board.len());
-            }
+        return problems;
+    };
 
-            board.push(pipe);
-        }
+    match starts(&board)[..] {
+        [start] => start_positions(&board, start).err().into_iter().collect(),
+        [] => vec![ParseError::at_end(input, "expected a start ('S')")],
+        [_, ref others @ ..] => others
+            .iter()
+            .map(|x| error_at(&board, *x, "expected only one start"))
+            .collect(),
     }
+}
 
-    let width = width.unwrap();
-    let start = start.unwrap();
-
-    // Replace start with the pipe it is underneath!
-    let (x, y) = (start % width, start / width);
-
+/// The two positions next to the start that connect to it, heading away from it.
+fn start_positions(board: &Grid<Pipe>, start: Point<usize>) -> Result<Vec<Position>, ParseError> {
     let mut positions = Vec::new();
 
-    // If there is a pipe coming from the left (that pipe will be a right!).
-    if (0 != x) && board[(y * width) + (x - 1)].is_right() {
-        positions.push((Direction::Left, x - 1, y));
+    // A pipe to our left has to connect on its right, and so on.
+    for direction in [
+        Direction::Left,
+        Direction::Right,
+        Direction::Up,
+        Direction::Down,
+    ] {
+        if let Some(point) = board
+            .step(start, direction)
+            .filter(|x| board[*x].connects(direction.opposite()))
+        {
+            positions.push((direction, point));
+        }
     }
 
-    // If there is a pipe coming from the right (that pipe will be a left!).
-    if ((width - 1) != x) && board[(y * width) + (x + 1)].is_left() {
-        positions.push((Direction::Right, x + 1, y));
+    if 2 != positions.len() {
+        return Err(error_at(
+            board,
+            start,
+            "expected exactly two pipes to connect to the start",
+        ));
     }
 
-    // If there is a pipe coming from up (that pipe will be a down!).
-    if (0 != y) && board[((y - 1) * width) + x].is_down() {
-        positions.push((Direction::Up, x, y - 1));
-    }
+    Ok(positions)
+}
 
-    // If there is a pipe coming from down (that pipe will be an up!).
-    if ((width - 1) != y) && board[((y + 1) * width) + x].is_up() {
-        positions.push((Direction::Down, x, y + 1));
-    }
+/// Moves one step on along the pipe at `position`.
+fn follow(board: &Grid<Pipe>, (direction, point): Position) -> Result<Position, ParseError> {
+    let pipe = board[point];
+
+    // We came in through the opposite side, so we leave through whichever other side is open.
+    let next = [direction, direction.turn_left(), direction.turn_right()]
+        .into_iter()
+        .find(|x| pipe.connects(direction.opposite()) && pipe.connects(*x))
+        .ok_or_else(|| error_at(board, point, "expected the loop to continue here"))?;
+
+    board
+        .step(point, next)
+        .map(|x| (next, x))
+        .ok_or_else(|| error_at(board, point, "expected the loop to stay on the map"))
+}
 
-    assert_eq!(2, positions.len());
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
+    let (board, start) = parse_board(input)?;
+
+    let mut positions = start_positions(&board, start)?;
 
     let mut steps = 1;
 
     // While the positions do not match, we need to keep calculating.
-    while (positions[0].1 != positions[1].1) || (positions[0].2 != positions[1].2) {
+    while positions[0].1 != positions[1].1 {
         for position in positions.iter_mut() {
-            let direction = position.0;
-            let x = position.1;
-            let y = position.2;
-
-            let index = (y * width) + x;
-
-            *position = match direction {
-                Direction::Up => match board[index] {
-                    Pipe::UpAndDown => (Direction::Up, x, y - 1),
-                    Pipe::LeftAndDown => (Direction::Left, x - 1, y),
-                    Pipe::RightAndDown => (Direction::Right, x + 1, y),
-                    x => panic!("{:?}", x),
-                },
-                Direction::Down => match board[index] {
-                    Pipe::UpAndDown => (Direction::Down, x, y + 1),
-                    Pipe::LeftAndUp => (Direction::Left, x - 1, y),
-                    Pipe::RightAndUp => (Direction::Right, x + 1, y),
-                    x => panic!("{:?}", x),
-                },
-                Direction::Left => match board[index] {
-                    Pipe::LeftAndRight => (Direction::Left, x - 1, y),
-                    Pipe::RightAndUp => (Direction::Up, x, y - 1),
-                    Pipe::RightAndDown => (Direction::Down, x, y + 1),
-                    x => panic!("{:?}", x),
-                },
-                Direction::Right => match board[index] {
-                    Pipe::LeftAndRight => (Direction::Right, x + 1, y),
-                    Pipe::LeftAndUp => (Direction::Up, x, y - 1),
-                    Pipe::LeftAndDown => (Direction::Down, x, y + 1),
-                    x => panic!("{:?}", x),
-                },
-            };
+            *position = follow(&board, *position)?;
         }
 
         steps += 1;
     }
 
-    Solution::U32(steps)
+    Ok(Solution::U32(steps))
 }
 
-pub fn solve2(input: &str) -> Solution {
-    let mut board = Vec::new();
-    let mut width = None;
-    let mut start = None;
-
-    for line in input.lines() {
-        if let Some(width) = width {
-            assert_eq!(width, line.len());
-        } else {
-            width = Some(line.len());
-        }
-
-        for c in line.chars() {
-            let pipe = Pipe::from(c);
-
-            if Pipe::Start == pipe {
-                assert!(start.is_none());
-                start = Some(board.len());
-            }
-
-            board.push(pipe);
-        }
-    }
-
-    let width = width.unwrap();
-    let start = start.unwrap();
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
+    let (mut board, start) = parse_board(input)?;
 
     // Replace start with the pipe it is underneath!
-    let (x, y) = (start % width, start / width);
-
-    let mut positions = Vec::new();
-
-    // If there is a pipe coming from the left (that pipe will be a right!).
-    if (0 != x) && board[(y * width) + (x - 1)].is_right() {
-        positions.push((Direction::Left, x - 1, y));
-    }
-
-    // If there is a pipe coming from the right (that pipe will be a left!).
-    if ((width - 1) != x) && board[(y * width) + (x + 1)].is_left() {
-        positions.push((Direction::Right, x + 1, y));
-    }
-
-    // If there is a pipe coming from up (that pipe will be a down!).
-    if (0 != y) && board[((y - 1) * width) + x].is_down() {
-        positions.push((Direction::Up, x, y - 1));
-    }
-
-    // If there is a pipe coming from down (that pipe will be an up!).
-    if ((width - 1) != y) && board[((y + 1) * width) + x].is_up() {
-        positions.push((Direction::Down, x, y + 1));
-    }
-
-    assert_eq!(2, positions.len());
-
-    positions.sort();
-
-    board[start] = match positions[0].0 {
-        Direction::Up => match positions[1].0 {
-            Direction::Down => Pipe::UpAndDown,
-            Direction::Left => Pipe::LeftAndUp,
-            Direction::Right => Pipe::RightAndUp,
-            _ => unreachable!(),
-        },
-        Direction::Down => match positions[1].0 {
-            Direction::Left => Pipe::LeftAndDown,
-            Direction::Right => Pipe::RightAndDown,
-            _ => unreachable!(),
-        },
-        Direction::Left => match positions[1].0 {
-            Direction::Right => Pipe::LeftAndRight,
-            _ => unreachable!(),
-        },
-        _ => unreachable!(),
-    };
-
-    let mut main_loop = Vec::new();
-    main_loop.resize(board.len(), false);
+    let positions = start_positions(&board, start)?;
+
+    board[start] = SYMBOLS
+        .iter()
+        .map(|(_, pipe)| *pipe)
+        .find(|pipe| {
+            positions
+                .iter()
+                .all(|(direction, _)| pipe.connects(*direction))
+        })
+        .unwrap();
+
+    let mut main_loop = Grid::filled(board.width(), board.height(), false);
     main_loop[start] = true;
 
     // While the positions do not match, we need to keep calculating.
     let mut position = positions[0];
 
-    loop {
-        let direction = position.0;
-        let x = position.1;
-        let y = position.2;
-
-        let index = (y * width) + x;
-
-        if main_loop[index] {
-            break;
-        }
+    while !main_loop[position.1] {
+        main_loop[position.1] = true;
 
-        let pipe = board[index];
-
-        main_loop[index] = true;
-
-        position = match direction {
-            Direction::Up => match pipe {
-                Pipe::UpAndDown => (Direction::Up, x, y - 1),
-                Pipe::LeftAndDown => (Direction::Left, x - 1, y),
-                Pipe::RightAndDown => (Direction::Right, x + 1, y),
-                x => panic!("{:?}", x),
-            },
-            Direction::Down => match pipe {
-                Pipe::UpAndDown => (Direction::Down, x, y + 1),
-                Pipe::LeftAndUp => (Direction::Left, x - 1, y),
-                Pipe::RightAndUp => (Direction::Right, x + 1, y),
-                x => panic!("{:?}", x),
-            },
-            Direction::Left => match pipe {
-                Pipe::LeftAndRight => (Direction::Left, x - 1, y),
-                Pipe::RightAndUp => (Direction::Up, x, y - 1),
-                Pipe::RightAndDown => (Direction::Down, x, y + 1),
-                x => panic!("{:?}", x),
-            },
-            Direction::Right => match pipe {
-                Pipe::LeftAndRight => (Direction::Right, x + 1, y),
-                Pipe::LeftAndUp => (Direction::Up, x, y - 1),
-                Pipe::LeftAndDown => (Direction::Down, x, y + 1),
-                x => panic!("{:?}", x),
-            },
-        };
+        position = follow(&board, position)?;
     }
 
     let mut inside_count = 0;
 
-    for y in 0..(board.len() / width) {
+    for (pipes, on_loop) in board.rows().zip(main_loop.rows()) {
         let mut outside = true;
 
-        for x in 0..width {
-            let index = y * width + x;
-
-            if main_loop[index] {
-                if matches!(
-                    board[index],
-                    Pipe::UpAndDown | Pipe::LeftAndDown | Pipe::RightAndDown
-                ) {
+        for (pipe, on_loop) in pipes.iter().zip(on_loop) {
+            if *on_loop {
+                if pipe.connects(Direction::Down) {
                     outside = !outside;
                 }
             } else if !outside {
@@ -310,85 +246,106 @@ pub fn solve2(input: &str) -> Solution {
         }
     }
 
-    Solution::U32(inside_count)
+    Ok(Solution::U32(inside_count))
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day10"));
+pub struct Day10;
+
+impl Day for Day10 {
+    fn number(&self) -> u8 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
 
-    (solve1(input), solve2(input))
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        validate(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE1: &str = include_str!("../../examples/day10-1");
+    const EXAMPLE2: &str = include_str!("../../examples/day10-2");
+    const EXAMPLE3: &str = include_str!("../../examples/day10-3");
+    const EXAMPLE4: &str = include_str!("../../examples/day10-4");
+    const EXAMPLE5: &str = include_str!("../../examples/day10-5");
+    const EXAMPLE6: &str = include_str!("../../examples/day10-6");
+
     #[test]
     fn it_works() {
-        let input = r"-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF";
-
-        assert_eq!(super::solve1(input), Solution::U32(4));
-
-        let input = r"..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-
-        assert_eq!(super::solve1(input), Solution::U32(8));
-
-        let input = r"...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-
-        assert_eq!(super::solve2(input), Solution::U32(4));
-
-        let input = r"..........
-.S------7.
-.|F----7|.
-.||....||.
-.||....||.
-.|L-7F-J|.
-.|..||..|.
-.L--JL--J.
-..........";
-
-        assert_eq!(super::solve2(input), Solution::U32(4));
-
-        let input = r".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...";
-
-        assert_eq!(super::solve2(input), Solution::U32(8));
-
-        let input = r"FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L";
-
-        assert_eq!(super::solve2(input), Solution::U32(10));
+        let input = EXAMPLE1;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(4)));
+
+        let input = EXAMPLE2;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U32(8)));
+
+        let input = EXAMPLE3;
+
+        assert_eq!(super::solve2(input), Ok(Solution::U32(4)));
+
+        let input = EXAMPLE4;
+
+        assert_eq!(super::solve2(input), Ok(Solution::U32(4)));
+
+        let input = EXAMPLE5;
+
+        assert_eq!(super::solve2(input), Ok(Solution::U32(8)));
+
+        let input = EXAMPLE6;
+
+        assert_eq!(super::solve2(input), Ok(Solution::U32(10)));
+
+        let error = super::solve1(".....\n.S-7.\n.|.|.\n.L-J.\n..x..").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (5, 3, "x"));
+
+        let error = super::solve1(".....\n.S-7.\n.|.|.\n.L.J.\n.....").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (4, 3, "."));
+
+        let problems = super::validate(".S-7.\n.|x|.\n.L-JS\nS....");
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(2, 3), (3, 5), (4, 1)]
+        );
+
+        let problems = super::validate("..\n.x.");
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(2, 1), (2, 2), (3, 1)]
+        );
+
+        let problems = super::validate(".S-7.\n.|.|.\n.L-JS\nS....");
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(3, 5), (4, 1)]
+        );
     }
 }
@@ -2,125 +2,149 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::{Grid, Point};
+use crate::{Day, ParseError, Parsed, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solver(input: &str, empty_row_col_cost: usize) -> Solution {
-    // -1 because we already count the row/col once implicitly.
-    let empty_row_col_cost = empty_row_col_cost - 1;
-
-    let mut board = Vec::new();
-    let mut width = None;
-
-    for line in input.lines() {
-        if let Some(width) = width {
-            assert_eq!(width, line.len());
-        } else {
-            width = Some(line.len());
-        }
-
-        for c in line.chars() {
-            match c {
-                '#' => {
-                    board.push(true);
-                }
-                '.' => {
-                    board.push(false);
-                }
-                _ => unreachable!(),
-            }
-        }
-    }
+struct Image {
+    galaxies: Vec<Point<usize>>,
+    expanded_rows: Vec<bool>,
+    expanded_columns: Vec<bool>,
+}
 
-    let board = board;
-    let galaxies: Vec<usize> = board
-        .iter()
-        .enumerate()
-        .filter(|(_, x)| **x)
-        .map(|(index, _)| index)
-        .collect();
-    let width = width.unwrap();
+impl Image {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let board = Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
 
-    let mut expanded_rows = Vec::new();
+        let galaxies = board
+            .iter()
+            .filter(|(_, x)| **x)
+            .map(|(position, _)| position)
+            .collect();
 
-    for row in 0..(board.len() / width) {
-        let offset = row * width;
-        expanded_rows.push(board[offset..(offset + width)].iter().all(|x| !*x));
-    }
+        let expanded_rows = board.rows().map(|x| x.iter().all(|x| !*x)).collect();
 
-    let mut expanded_columns = Vec::new();
+        let expanded_columns = board.columns().map(|mut x| x.all(|x| !*x)).collect();
 
-    for col in 0..width {
-        expanded_columns.push(board[col..].iter().step_by(width).all(|x| !*x));
+        Ok(Image {
+            galaxies,
+            expanded_rows,
+            expanded_columns,
+        })
     }
 
-    let expanded_rows = expanded_rows;
-    let expanded_columns = expanded_columns;
+    fn sum_of_distances(&self, empty_row_col_cost: usize) -> u64 {
+        // -1 because we already count the row/col once implicitly.
+        let empty_row_col_cost = empty_row_col_cost - 1;
 
-    let mut shortest_distances = HashMap::new();
+        let mut shortest_distances = HashMap::new();
 
-    for (g, o) in galaxies.iter().tuple_combinations() {
-        let (gx, gy) = (g % width, g / width);
-        let (ox, oy) = (o % width, o / width);
-
-        let distance = gx.abs_diff(ox) + gy.abs_diff(oy);
-
-        let column_expansion = expanded_columns[gx.min(ox)..=gx.max(ox)]
+        for ((g, gp), (o, op)) in self
+            .galaxies
             .iter()
-            .filter(|x| **x)
-            .count();
-        let row_expansion = expanded_rows[gy.min(oy)..=gy.max(oy)]
-            .iter()
-            .filter(|x| **x)
-            .count();
-
-        let distance = (distance + empty_row_col_cost * (column_expansion + row_expansion)) as u64;
+            .copied()
+            .enumerate()
+            .tuple_combinations()
+        {
+            let distance = gp.manhattan(op);
+
+            let column_expansion = self.expanded_columns[gp.x.min(op.x)..=gp.x.max(op.x)]
+                .iter()
+                .filter(|x| **x)
+                .count();
+            let row_expansion = self.expanded_rows[gp.y.min(op.y)..=gp.y.max(op.y)]
+                .iter()
+                .filter(|x| **x)
+                .count();
+
+            let distance =
+                (distance + empty_row_col_cost * (column_expansion + row_expansion)) as u64;
+
+            let mut pair = [g, o];
+            pair.sort();
+
+            let recorded_distance = shortest_distances.entry(pair).or_insert(distance);
+
+            assert_eq!(*recorded_distance, distance);
+        }
 
-        let mut pair = [g, o];
-        pair.sort();
+        shortest_distances.values().sum::<u64>()
+    }
+}
 
-        let recorded_distance = shortest_distances.entry(pair).or_insert(distance);
+impl Parsed for Image {
+    fn part1(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U64(self.sum_of_distances(2)))
+    }
 
-        assert_eq!(*recorded_distance, distance);
+    fn part2(&self) -> Result<Solution, ParseError> {
+        Ok(Solution::U64(self.sum_of_distances(1000000)))
     }
+}
 
-    Solution::U64(shortest_distances.values().sum::<u64>())
+pub fn solver(input: &str, empty_row_col_cost: usize) -> Result<Solution, ParseError> {
+    Ok(Solution::U64(
+        Image::parse(input)?.sum_of_distances(empty_row_col_cost),
+    ))
 }
 
-pub fn solve1(input: &str) -> Solution {
+pub fn solve1(input: &str) -> Result<Solution, ParseError> {
     solver(input, 2)
 }
 
-pub fn solve2(input: &str) -> Solution {
+pub fn solve2(input: &str) -> Result<Solution, ParseError> {
     solver(input, 1000000)
 }
 
-pub fn solve() -> SolutionPair {
-    let input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input/day11"));
+pub struct Day11;
+
+impl Day for Day11 {
+    fn number(&self) -> u8 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
+
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        Grid::<bool>::problems(input, |c| matches!(c, '#' | '.'))
+    }
 
-    (solve1(input), solve2(input))
+    fn parse<'a>(&self, input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(Some(Box::new(Image::parse(input)?)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
 
+    const EXAMPLE: &str = include_str!("../../examples/day11-1");
+
     #[test]
     fn it_works() {
-        let input = r"...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        assert_eq!(super::solve1(input), Solution::U64(374));
-        assert_eq!(super::solver(input, 10), Solution::U64(1030));
-        assert_eq!(super::solver(input, 100), Solution::U64(8410));
+        let input = EXAMPLE;
+
+        assert_eq!(super::solve1(input), Ok(Solution::U64(374)));
+        assert_eq!(super::solver(input, 10), Ok(Solution::U64(1030)));
+        assert_eq!(super::solver(input, 100), Ok(Solution::U64(8410)));
+
+        let error = super::solve1("#..\n.#.\n..").unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 1));
     }
 }
@@ -1,11 +1,37 @@
-use crate::{Solution, SolutionPair};
+use crate::{Day, ParseError, Solution};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve() -> SolutionPair {
+pub fn solve1(_input: &str) -> Result<Solution, ParseError> {
     // Your solution here...
-    let sol1: u64 = 0;
-    let sol2: u64 = 0;
+    Ok(Solution::Unsolved)
+}
+
+pub fn solve2(_input: &str) -> Result<Solution, ParseError> {
+    // Your solution here...
+    Ok(Solution::Unsolved)
+}
+
+pub struct Day14;
+
+impl Day for Day14 {
+    fn number(&self) -> u8 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Parabolic Reflector Dish"
+    }
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError> {
+        solve1(input)
+    }
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError> {
+        solve2(input)
+    }
 
-    (Solution::U64(sol1), Solution::U64(sol2))
+    fn is_implemented(&self) -> bool {
+        false
+    }
 }
@@ -1,3 +1,5 @@
+use crate::Day;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -23,3 +25,75 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+
+macro_rules! days {
+    ($($module:ident :: $day:ident),* $(,)?) => {
+        /// Every registered day solver, in the order they should be run.
+        pub static ALL: &[&dyn Day] = &[$(&$module::$day),*];
+    };
+}
+
+days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+    day24::Day24,
+    day25::Day25,
+}
+
+pub fn get(number: u8) -> Option<&'static dyn Day> {
+    ALL.iter().copied().find(|day| number == day.number())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples;
+    use std::path::Path;
+
+    #[test]
+    fn examples_give_expected_answers() {
+        for day in super::ALL {
+            // Days without any examples yet are fine.
+            let Ok(examples) = examples::load(Path::new(examples::DEFAULT_DIR), day.number())
+            else {
+                continue;
+            };
+
+            for example in examples {
+                let name = format!("Day {} example {}", day.number(), example.number);
+
+                if let Some(expected) = example.answers.part1 {
+                    let actual = day.part1(&example.input).map(|x| x.to_string());
+
+                    assert_eq!(actual, Ok(expected), "{}", name);
+                }
+
+                if let Some(expected) = example.answers.part2 {
+                    let actual = day.part2(&example.input).map(|x| x.to_string());
+
+                    assert_eq!(actual, Ok(expected), "{}", name);
+                }
+            }
+        }
+    }
+}
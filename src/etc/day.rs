@@ -0,0 +1,45 @@
+use crate::{ParseError, Solution};
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A puzzle input that has been parsed once so both parts can share the work.
+pub trait Parsed: Sync {
+    fn part1(&self) -> Result<Solution, ParseError>;
+
+    fn part2(&self) -> Result<Solution, ParseError>;
+}
+
+pub trait Day: Sync {
+    fn number(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn part1(&self, input: &str) -> Result<Solution, ParseError>;
+
+    fn part2(&self, input: &str) -> Result<Solution, ParseError>;
+
+    /// Days that separate parsing from solving return their parsed input here, so that the
+    /// runner can time the parsing on its own.
+    fn parse<'a>(&self, _input: &'a str) -> Result<Option<Box<dyn Parsed + 'a>>, ParseError> {
+        Ok(None)
+    }
+
+    /// Every problem with the structure of `input`, rather than just the first one that solving
+    /// trips over. Without a day specific check, this is whatever stops the input from parsing.
+    /// It never solves anything, since `--check-input` doesn't run days in isolation and a part
+    /// could panic or loop on a bad input.
+    fn validate(&self, input: &str) -> Vec<ParseError> {
+        self.parse(input).err().into_iter().collect()
+    }
+
+    /// Days with settings (looked up with `params::get`) check them here, so that a bad value is
+    /// reported before the day runs.
+    fn check_params(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Stub days that have not been solved yet return `false` here.
+    fn is_implemented(&self) -> bool {
+        true
+    }
+}
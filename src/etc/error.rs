@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Where and why a puzzle input could not be understood.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, since the parsers themselves don't know which day they are.
+    pub day: Option<u8>,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, counted in chars.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            day: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error just past the last line, for inputs that stop too soon.
+    pub fn at_end(input: &str, message: impl Into<String>) -> Self {
+        ParseError::new(input.lines().count() + 1, 1, "", message)
+    }
+
+    /// An error about something other than the input, like a day's parameters, so it isn't at
+    /// any line or column.
+    pub fn elsewhere(text: &str, message: impl Into<String>) -> Self {
+        ParseError::new(0, 0, text, message)
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(day) = self.day {
+            write!(f, "day {:02}, ", day)?;
+        }
+
+        if 0 == self.line {
+            return write!(f, "{} (found '{}')", self.message, self.text);
+        }
+
+        write!(
+            f,
+            "line {}, column {}: {} (found '{}')",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the puzzle input that knows where it is, so that errors about any slice of it can
+/// say exactly where they happened.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// 0-based.
+    pub index: usize,
+    pub text: &'a str,
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line { index, text })
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `at`, which should be a slice of this line (otherwise the error
+    /// points at the start of the line).
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize).wrapping_sub(start);
+
+        let column = if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        };
+
+        ParseError::new(self.index + 1, column, at, message)
+    }
+
+    /// An error pointing at the char at byte `offset` into this line.
+    pub fn error_at(&self, offset: usize, message: impl Into<String>) -> ParseError {
+        let at = self.text[offset..]
+            .chars()
+            .next()
+            .map_or("", |c| &self.text[offset..(offset + c.len_utf8())]);
+
+        self.error(at, message)
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: char,
+    ) -> std::result::Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, format!("expected '{}'", delimiter)))
+    }
+
+    pub fn strip_prefix(
+        &self,
+        s: &'a str,
+        prefix: &str,
+    ) -> std::result::Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format!("expected '{}'", prefix)))
+    }
+
+    pub fn number<T: FromStr>(&self, s: &'a str) -> std::result::Result<T, ParseError> {
+        s.parse::<T>()
+            .map_err(|_| self.error(s, "expected a number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        let line = super::lines("Game 1: 3 blue\nGame x: 1 red")
+            .nth(1)
+            .unwrap();
+
+        let error = line.number::<u32>(&line.text[5..6]).unwrap_err().in_day(2);
+
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(
+            error.to_string(),
+            "day 02, line 2, column 6: expected a number (found 'x')"
+        );
+
+        let error = super::ParseError::elsewhere("x", "expected a bag").in_day(2);
+
+        assert_eq!(error.to_string(), "day 02, expected a bag (found 'x')");
+
+        let error = line.split_once(line.text, ';').unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
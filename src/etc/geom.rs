@@ -0,0 +1,170 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Sub};
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A point on a grid, with y growing downwards like the rows of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The number of steps between the points when moving only up, down, left and right.
+    pub fn manhattan(self, other: Self) -> T {
+        let distance = |a: T, b: T| a.max(b) - a.min(b);
+
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+}
+
+impl Point<usize> {
+    /// This point moved by `delta`, unless that would go below zero.
+    pub fn offset(self, delta: Point<isize>) -> Option<Self> {
+        Some(Point::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+
+    /// One step in `direction`, if that stays within `bounds`.
+    pub fn step(self, direction: Direction, bounds: &Rect<usize>) -> Option<Self> {
+        self.offset(direction.delta())
+            .filter(|x| bounds.contains(*x))
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// How one step in this direction moves a point.
+    pub fn delta(self) -> Point<isize> {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+        }
+    }
+}
+
+/// The points from `min` up to (but not including) `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Copy + PartialOrd> Rect<T> {
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (self.min.x..self.max.x).contains(&point.x) && (self.min.y..self.max.y).contains(&point.y)
+    }
+}
+
+impl Rect<usize> {
+    /// The rectangle from (0, 0) that is `width` by `height`.
+    pub fn sized(width: usize, height: usize) -> Self {
+        Rect {
+            min: Point::new(0, 0),
+            max: Point::new(width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Point, Rect};
+
+    #[test]
+    fn it_works() {
+        let bounds = Rect::sized(3, 2);
+
+        let p = Point::new(0usize, 1);
+
+        assert_eq!(p.step(Direction::Right, &bounds), Some(Point::new(1, 1)));
+        assert_eq!(p.step(Direction::Left, &bounds), None);
+        assert_eq!(p.step(Direction::Down, &bounds), None);
+
+        assert_eq!(Point::new(1usize, 5).manhattan(Point::new(4, 2)), 6);
+        assert_eq!(Point::new(-1, 5).manhattan(Point::new(4, -2)), 12);
+        assert_eq!(Point::new(1, 2) + Point::new(3, 4), Point::new(4, 6));
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(
+                direction.delta() + direction.opposite().delta(),
+                Point::new(0, 0)
+            );
+        }
+    }
+}
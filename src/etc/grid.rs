@@ -0,0 +1,305 @@
+use crate::etc::geom::{Direction, Point, Rect};
+use crate::etc::{lines, ParseError};
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A rectangular grid of cells, stored row by row, with (0, 0) in the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+const NEIGHBOURS8: [Point<isize>; 8] = [
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(-1, 1),
+    Point::new(0, 1),
+    Point::new(1, 1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len());
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one cell per char, one row per line. `cell` returns `None` for chars that can't be
+    /// in the grid.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in lines(input) {
+            let len = line.text.chars().count();
+
+            match width {
+                Some(width) if width != len => {
+                    return Err(line.error(line.text, format!("expected a row of {} cells", width)));
+                }
+                _ => width = Some(len),
+            }
+
+            for (index, c) in line.text.char_indices() {
+                cells.push(cell(c).ok_or_else(|| line.error_at(index, "unexpected character"))?);
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if 0 != width => Ok(Grid::new(width, height, cells)),
+            _ => Err(ParseError::at_end(input, "expected a grid")),
+        }
+    }
+
+    /// Every problem `parse` would find with `input`, rather than just the first: rows of the wrong
+    /// width (compared to the first row) and chars that `is_cell` rejects.
+    pub fn problems(input: &str, mut is_cell: impl FnMut(char) -> bool) -> Vec<ParseError> {
+        let mut problems = Vec::new();
+        let mut width = None;
+
+        for line in lines(input) {
+            let len = line.text.chars().count();
+
+            match width {
+                Some(width) if width != len => problems
+                    .push(line.error(line.text, format!("expected a row of {} cells", width))),
+                Some(_) => (),
+                None => width = Some(len),
+            }
+
+            for (index, c) in line.text.char_indices() {
+                if !is_cell(c) {
+                    problems.push(line.error_at(index, "unexpected character"));
+                }
+            }
+        }
+
+        if width.is_none_or(|x| 0 == x) {
+            problems.push(ParseError::at_end(input, "expected a grid"));
+        }
+
+        problems
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Rect<usize> {
+        Rect::sized(self.width, self.height)
+    }
+
+    pub fn contains(&self, point: Point<usize>) -> bool {
+        self.bounds().contains(point)
+    }
+
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * self.width + point.x])
+    }
+
+    /// `point` moved by `delta`, if that is still on the grid.
+    pub fn offset(&self, point: Point<usize>, delta: Point<isize>) -> Option<Point<usize>> {
+        point.offset(delta).filter(|x| self.contains(*x))
+    }
+
+    /// `point` moved one step in `direction`, if that is still on the grid.
+    pub fn step(&self, point: Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        point.step(direction, &self.bounds())
+    }
+
+    /// The up to 4 cells that share an edge with `point`.
+    pub fn neighbours4(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// The up to 8 cells that share an edge or a corner with `point`.
+    pub fn neighbours8(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |delta| self.offset(point, delta))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (Point::new(index % self.width, index / self.width), cell))
+    }
+
+    /// The position of the first cell (row by row) that matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point<usize>> {
+        self.iter().find(|(_, x)| predicate(x)).map(|(p, _)| p)
+    }
+
+    /// A new grid with `func` applied to every cell.
+    pub fn map<U>(&self, func: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(
+            self.width,
+            self.height,
+            self.cells.iter().map(func).collect(),
+        )
+    }
+
+    /// Flips the grid over its top left to bottom right diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+
+        Grid::new(self.height, self.width, cells)
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| {
+                (0..self.height)
+                    .rev()
+                    .map(move |y| self[Point::new(x, y)].clone())
+            })
+            .collect();
+
+        Grid::new(self.height, self.width, cells)
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| (0..self.height).map(move |y| self[Point::new(x, y)].clone()))
+            .collect();
+
+        Grid::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is off the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is off the grid", point))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::etc::geom::Point;
+
+    #[test]
+    fn it_works() {
+        let grid = Grid::parse("ab\ncd\nef", Some).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "bdf\nace\n");
+
+        let error = Grid::parse("ab\nc", Some).unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = Grid::parse("ab\ncx", |c| (c != 'x').then_some(c)).unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+
+        let problems = Grid::<char>::problems("ab\nx\nabc", |c| 'x' != c);
+
+        assert_eq!(
+            problems
+                .iter()
+                .map(|x| (x.line, x.column))
+                .collect::<Vec<_>>(),
+            [(2, 1), (2, 1), (3, 1)]
+        );
+    }
+}
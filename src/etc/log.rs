@@ -0,0 +1,25 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+///////////////////////////////////////////////////////////////////////////////
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Turns the `verbose!` logging on or off for the whole process.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Like `eprintln!`, but only when running with `--verbose`. Logging goes to stderr so that it
+/// never mixes with a JSON or CSV report.
+#[macro_export]
+macro_rules! verbose {
+    ($($arg:tt)*) => {
+        if $crate::etc::log::is_verbose() {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Finds every occurrence of a set of words in a single pass over the text, including ones that
+/// overlap (like the "eight" and "two" in "eightwo"). This is an Aho-Corasick automaton with the
+/// full transition table filled in, so each byte of the text is one lookup.
+pub struct Matcher<T> {
+    next: Vec<[usize; 256]>,
+    /// The length and value of every word that ends at each state.
+    found: Vec<Vec<(usize, T)>>,
+}
+
+impl<T: Clone> Matcher<T> {
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, T)>) -> Self {
+        const NONE: usize = usize::MAX;
+
+        let mut next = vec![[NONE; 256]];
+        let mut found = vec![Vec::new()];
+
+        // First a trie of the words.
+        for (word, value) in words {
+            let mut state = 0;
+
+            for byte in word.bytes() {
+                if NONE == next[state][byte as usize] {
+                    next[state][byte as usize] = next.len();
+                    next.push([NONE; 256]);
+                    found.push(Vec::new());
+                }
+
+                state = next[state][byte as usize];
+            }
+
+            found[state].push((word.len(), value));
+        }
+
+        // Then breadth first, so that the state for the longest proper suffix of each state's text
+        // is always finished before the state itself, fill in where every missing byte goes.
+        let mut fail = vec![0; next.len()];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(state) = queue.pop_front() {
+            // Where each byte goes from the longest proper suffix, which is the root for the root.
+            let suffixes = if 0 == state {
+                [0; 256]
+            } else {
+                next[fail[state]]
+            };
+
+            for (byte, suffix) in suffixes.into_iter().enumerate() {
+                let child = next[state][byte];
+
+                if NONE == child {
+                    next[state][byte] = suffix;
+                } else {
+                    fail[child] = suffix;
+
+                    let inherited = found[suffix].clone();
+                    found[child].extend(inherited);
+
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        Matcher { next, found }
+    }
+
+    /// The start and value of every word in `text`, in the order that the words end.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, T)> + 'a {
+        text.bytes()
+            .enumerate()
+            .scan(0, move |state, (index, byte)| {
+                *state = self.next[*state][byte as usize];
+                Some((index + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.found[state]
+                    .iter()
+                    .map(move |(len, value)| (end - len, value.clone()))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matcher;
+
+    #[test]
+    fn it_works() {
+        let matcher = Matcher::new([("one", 1), ("two", 2), ("eight", 8), ("ne", 0), ("2", 2)]);
+
+        let found: Vec<_> = matcher.find_iter("eightwone2").collect();
+
+        assert_eq!(found, [(0, 8), (4, 2), (6, 1), (7, 0), (9, 2)]);
+        assert_eq!(matcher.find_iter("on tw").count(), 0);
+
+        let matcher = Matcher::new([("fünf", 5), ("ü", 0)]);
+
+        assert_eq!(
+            matcher.find_iter("xfünf").collect::<Vec<_>>(),
+            [(2, 0), (1, 5)]
+        );
+    }
+}
@@ -1,3 +1,20 @@
+pub mod day;
+pub mod error;
+pub mod geom;
+pub mod grid;
+pub mod log;
+pub mod matcher;
+pub mod ocr;
+pub mod params;
+pub mod parse;
+pub mod ranges;
 pub mod solution;
 
+pub use day::{Day, Parsed};
+pub use error::{lines, Line, ParseError};
+pub use geom::{Direction, Point, Rect};
+pub use grid::Grid;
+pub use matcher::Matcher;
+pub use ocr::ocr;
+pub use ranges::RangeMap;
 pub use solution::Solution;
@@ -0,0 +1,89 @@
+use crate::etc::{Grid, Point};
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The letters AoC draws in its 6 pixel tall block font, each trimmed to the columns it uses.
+const LETTERS: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the block letters drawn in `grid`, which must be 6 cells tall. Letters are split at the
+/// empty columns between them, so any spacing works. Returns `None` if a shape isn't a letter.
+pub fn ocr(grid: &Grid<bool>) -> Option<String> {
+    if 6 != grid.height() {
+        return None;
+    }
+
+    let lit: Vec<bool> = grid.columns().map(|mut x| x.any(|x| *x)).collect();
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < grid.width() {
+        if !lit[x] {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+
+        while x < grid.width() && lit[x] {
+            x += 1;
+        }
+
+        let glyph = |y: usize| -> String {
+            (start..x)
+                .map(|x| if grid[Point::new(x, y)] { '#' } else { '.' })
+                .collect()
+        };
+
+        let (letter, _) = LETTERS
+            .iter()
+            .find(|(_, rows)| rows.iter().enumerate().all(|(y, row)| *row == glyph(y)))?;
+
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::etc::Grid;
+
+    #[test]
+    fn it_works() {
+        let picture = "\
+#..#.###..####.####.###..
+#..#..#...#....#....#..#.
+####..#...###..###..#..#.
+#..#..#...#....#....###..
+#..#..#...#....#....#.#..
+#..#.###..####.#....#..#.";
+
+        let grid = Grid::parse(picture, |c| Some('#' == c)).unwrap();
+
+        assert_eq!(super::ocr(&grid).as_deref(), Some("HIEFR"));
+
+        let grid = Grid::parse("##\n##\n##\n##\n##\n##", |c| Some('#' == c)).unwrap();
+
+        assert_eq!(super::ocr(&grid), None);
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+///////////////////////////////////////////////////////////////////////////////
+
+static PARAMS: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Replaces the parameters days can look up with `get`, like `day02.bag`.
+pub fn set(params: impl IntoIterator<Item = (String, String)>) {
+    *PARAMS.write().unwrap() = Some(params.into_iter().collect());
+}
+
+/// The value of the parameter called `key`, if it was given with `--param` or in a `--config`
+/// file.
+pub fn get(key: &str) -> Option<String> {
+    PARAMS
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|x| x.get(key).cloned())
+}
+
+/// Splits a `key=value` parameter, as given to `--param`.
+pub fn parse_param(param: &str) -> Result<(String, String), String> {
+    let (key, value) = param
+        .split_once('=')
+        .ok_or_else(|| format!("Not a valid parameter: '{}' (expected key=value)", param))?;
+
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// The `key = value` lines of a config file. Blank lines and lines starting with `#` are skipped.
+pub fn parse_config(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| parse_param(line).map_err(|e| format!("line {}: {}", index + 1, e)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        let config = super::parse_config("# Bigger bag\nday02.bag = 20 red, 1 pink\n\n").unwrap();
+
+        assert_eq!(
+            config,
+            [("day02.bag".to_string(), "20 red, 1 pink".to_string())]
+        );
+
+        assert!(super::parse_config("a = 1\nb").is_err());
+        assert!(super::parse_param("x").is_err());
+    }
+}
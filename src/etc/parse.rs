@@ -0,0 +1,126 @@
+use crate::etc::{lines, Line, ParseError};
+use std::str::FromStr;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// The whitespace separated numbers in `text`, which should be a slice of `line`. Any token that
+/// isn't a number is an error.
+pub fn numbers<'a, T: FromStr>(line: &Line<'a>, text: &'a str) -> Result<Vec<T>, ParseError> {
+    text.split_whitespace().map(|x| line.number(x)).collect()
+}
+
+/// Every (possibly negative) integer in `text`, which should be a slice of `line`, ignoring
+/// whatever is between them. Only integers too big for `T` are an error.
+pub fn ints<'a, T: FromStr>(line: &Line<'a>, text: &'a str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+
+    let mut ints = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+
+        if b'-' == bytes[index] {
+            index += 1;
+        }
+
+        let digits = index;
+
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+
+        if digits == index {
+            index = start + 1;
+            continue;
+        }
+
+        ints.push(line.number(&text[start..index])?);
+    }
+
+    Ok(ints)
+}
+
+/// What follows `label:` at the start of the line, without the whitespace around it.
+pub fn strip_label<'a>(line: &Line<'a>, label: &str) -> Result<&'a str, ParseError> {
+    line.text
+        .strip_prefix(label)
+        .and_then(|x| x.strip_prefix(':'))
+        .map(str::trim)
+        .ok_or_else(|| line.error(line.text, format!("expected '{}:'", label)))
+}
+
+/// The numbers after `label:`, like the `seeds: 79 14 55 13` line of day 5.
+pub fn labelled_numbers<T: FromStr>(line: &Line, label: &str) -> Result<Vec<T>, ParseError> {
+    numbers(line, strip_label(line, label)?)
+}
+
+/// The key and the rest of a `label key: rest` line, like `Game 12: 3 blue, 4 red`.
+pub fn key_value<'a, K: FromStr>(line: &Line<'a>, label: &str) -> Result<(K, &'a str), ParseError> {
+    let text = line.strip_prefix(line.text, label)?;
+    let (key, value) = line.split_once(text, ':')?;
+
+    Ok((line.number(key.trim())?, value.trim()))
+}
+
+/// The lines of `input` split into sections at each blank line. Empty sections are skipped.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![Vec::new()];
+
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            sections.push(Vec::new());
+        } else {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+
+    sections.retain(|x| !x.is_empty());
+    sections
+}
+
+/// The problem with each line of `input` that `parse` rejects, for validating inputs where every
+/// line stands alone.
+pub fn line_problems<'a, T>(
+    input: &'a str,
+    mut parse: impl FnMut(&Line<'a>) -> Result<T, ParseError>,
+) -> Vec<ParseError> {
+    lines(input).filter_map(|x| parse(&x).err()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::etc::lines;
+
+    #[test]
+    fn it_works() {
+        let line = lines("Card  3:  1 21 | -5x7").next().unwrap();
+
+        let (id, rest) = super::key_value::<u8>(&line, "Card").unwrap();
+
+        assert_eq!((id, rest), (3, "1 21 | -5x7"));
+        assert_eq!(super::ints::<i32>(&line, rest).unwrap(), [1, 21, -5, 7]);
+        assert_eq!(super::numbers::<i32>(&line, &rest[..4]).unwrap(), [1, 21]);
+
+        let error = super::numbers::<i32>(&line, rest).unwrap_err();
+
+        assert_eq!((error.column, error.text.as_str()), (16, "|"));
+
+        let line = lines("Time:      7  15   30").next().unwrap();
+
+        assert_eq!(
+            super::labelled_numbers::<u32>(&line, "Time").unwrap(),
+            [7, 15, 30]
+        );
+        assert!(super::strip_label(&line, "Distance").is_err());
+
+        let sections = super::sections("a\nb\n\n\nc\n");
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!((sections[1][0].index, sections[1][0].text), (4, "c"));
+
+        let problems = super::line_problems("1\nx\n3\ny", |x| x.number::<u8>(x.text));
+
+        assert_eq!(problems.iter().map(|x| x.line).collect::<Vec<_>>(), [2, 4]);
+    }
+}
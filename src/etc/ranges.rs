@@ -0,0 +1,183 @@
+use std::ops::Range;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// A map from numbers to numbers that moves whole ranges of them by an offset, and leaves every
+/// number outside those ranges where it is. Maps compose with `then`, undo with `invert`, and
+/// move whole sets of ranges at once with `map_ranges`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /// The ranges that move and how far. They're sorted and don't overlap, no offset is zero, and
+    /// no two touching ranges have the same offset, so equal maps are always equal.
+    moves: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    pub fn identity() -> Self {
+        RangeMap::default()
+    }
+
+    /// The map that sends each source range to the range starting at its destination. The source
+    /// ranges shouldn't overlap.
+    pub fn new(ranges: impl IntoIterator<Item = (Range<i64>, i64)>) -> Self {
+        RangeMap::from_pieces(
+            ranges
+                .into_iter()
+                .map(|(source, destination)| {
+                    let offset = destination - source.start;
+                    (source, offset)
+                })
+                .collect(),
+        )
+    }
+
+    fn from_pieces(mut pieces: Vec<(Range<i64>, i64)>) -> Self {
+        pieces.retain(|(range, offset)| 0 != *offset && !range.is_empty());
+        pieces.sort_by_key(|(range, _)| range.start);
+
+        let mut moves: Vec<(Range<i64>, i64)> = Vec::new();
+
+        for (range, offset) in pieces {
+            match moves.last_mut() {
+                Some((last, last_offset)) if *last_offset == offset && last.end == range.start => {
+                    last.end = range.end
+                }
+                _ => moves.push((range, offset)),
+            }
+        }
+
+        RangeMap { moves }
+    }
+
+    /// Every number split into ranges by how far they move, including the ones that don't.
+    fn pieces(&self) -> Vec<(Range<i64>, i64)> {
+        let mut pieces = Vec::new();
+        let mut start = i64::MIN;
+
+        for (range, offset) in &self.moves {
+            if start < range.start {
+                pieces.push((start..range.start, 0));
+            }
+
+            pieces.push((range.clone(), *offset));
+            start = range.end;
+        }
+
+        if start < i64::MAX {
+            pieces.push((start..i64::MAX, 0));
+        }
+
+        pieces
+    }
+
+    pub fn get(&self, number: i64) -> i64 {
+        let index = self.moves.partition_point(|(range, _)| range.end <= number);
+
+        match self.moves.get(index) {
+            Some((range, offset)) if range.start <= number => number + offset,
+            _ => number,
+        }
+    }
+
+    /// Where all the numbers in `ranges` go, as sorted ranges with any that overlap or touch
+    /// joined together.
+    pub fn map_ranges(&self, ranges: impl IntoIterator<Item = Range<i64>>) -> Vec<Range<i64>> {
+        let pieces = self.pieces();
+
+        let mut mapped = Vec::new();
+
+        for range in ranges {
+            for (piece, offset) in &pieces {
+                let start = range.start.max(piece.start);
+                let end = range.end.min(piece.end);
+
+                if start < end {
+                    mapped.push((start + offset)..(end + offset));
+                }
+            }
+        }
+
+        mapped.sort_by_key(|x| x.start);
+
+        let mut joined: Vec<Range<i64>> = Vec::new();
+
+        for range in mapped {
+            match joined.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => joined.push(range),
+            }
+        }
+
+        joined
+    }
+
+    /// The map that does this one and then `next`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let after = next.pieces();
+
+        let mut pieces = Vec::new();
+
+        for (range, offset) in self.pieces() {
+            let moved = (range.start + offset)..(range.end + offset);
+
+            for (piece, next_offset) in &after {
+                let start = moved.start.max(piece.start);
+                let end = moved.end.min(piece.end);
+
+                if start < end {
+                    pieces.push(((start - offset)..(end - offset), offset + next_offset));
+                }
+            }
+        }
+
+        RangeMap::from_pieces(pieces)
+    }
+
+    /// The map that undoes this one, if no two numbers go to the same place.
+    pub fn invert(&self) -> Option<RangeMap> {
+        let mut pieces: Vec<_> = self
+            .pieces()
+            .into_iter()
+            .map(|(range, offset)| ((range.start + offset)..(range.end + offset), -offset))
+            .collect();
+
+        pieces.sort_by_key(|(range, _)| range.start);
+
+        if pieces.windows(2).any(|x| x[1].0.start < x[0].0.end) {
+            return None;
+        }
+
+        Some(RangeMap::from_pieces(pieces))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeMap;
+
+    #[test]
+    fn it_works() {
+        // Swaps 0..5 and 10..15.
+        let swap = RangeMap::new([(0..5, 10), (10..15, 0)]);
+
+        assert_eq!([-1, 0, 4, 5, 12].map(|x| swap.get(x)), [-1, 10, 14, 5, 2]);
+        assert_eq!(
+            swap.map_ranges([3..12, 20..22]),
+            [0..2, 5..10, 13..15, 20..22]
+        );
+        assert_eq!(swap.then(&swap), RangeMap::identity());
+        assert_eq!(swap.invert(), Some(swap.clone()));
+
+        let shift = RangeMap::new([(0..20, 100), (100..120, 0)]);
+        let both = swap.then(&shift);
+
+        assert_eq!(
+            [0, 5, 12, 20, 100].map(|x| both.get(x)),
+            [110, 105, 102, 20, 0]
+        );
+        assert_eq!(both.then(&both.invert().unwrap()), RangeMap::identity());
+
+        // 0..5 lands on top of 5..10, which doesn't move.
+        assert_eq!(RangeMap::new([(0..5, 5)]).invert(), None);
+    }
+}
@@ -1,7 +1,13 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, Result};
+use strum_macros::IntoStaticStr;
+
+use crate::etc::Grid;
 use Solution::*;
 
-#[derive(Debug, Eq, PartialEq)]
+/// An answer to one part of a day. Numeric variants compare by value, so `U32(13) == U64(13)`,
+/// and sort after `Unsolved` but before every `Str`, then `Lines`, then `List`.
+#[derive(Clone, Debug, IntoStaticStr)]
 pub enum Solution {
     I32(i32),
     I64(i64),
@@ -9,7 +15,141 @@ pub enum Solution {
     U32(u32),
     U64(u64),
     U128(u128),
+    BigInt(num::BigInt),
     Str(String),
+    /// A multi-line answer, like a picture of block letters, shown under the part heading.
+    Lines(Vec<String>),
+    /// Several values, like the `x,y` of a point, shown separated by commas.
+    List(Vec<Solution>),
+    /// What stub days return until they are solved, so it isn't mistaken for a real answer.
+    Unsolved,
+}
+
+impl Solution {
+    /// The name of the variant, e.g. `"U64"`, so reports can say what type an answer was.
+    pub fn kind(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Whether this is what a stub day returns rather than a real answer.
+    pub fn is_unsolved(&self) -> bool {
+        matches!(self, Unsolved)
+    }
+
+    /// The rows of `grid`, drawn with `#` and `.` like the puzzle text does.
+    pub fn picture(grid: &Grid<bool>) -> Self {
+        Lines(
+            grid.rows()
+                .map(|x| x.iter().map(|x| if *x { '#' } else { '.' }).collect())
+                .collect(),
+        )
+    }
+
+    /// The value of a numeric variant, or `None` for `Str`, `Lines`, `List` and `Unsolved`.
+    pub fn to_bigint(&self) -> Option<num::BigInt> {
+        Some(match self {
+            I32(x) => (*x).into(),
+            I64(x) => (*x).into(),
+            I128(x) => (*x).into(),
+            U32(x) => (*x).into(),
+            U64(x) => (*x).into(),
+            U128(x) => (*x).into(),
+            BigInt(x) => x.clone(),
+            Str(_) | Lines(_) | List(_) | Unsolved => return None,
+        })
+    }
+}
+
+impl PartialEq for Solution {
+    fn eq(&self, other: &Self) -> bool {
+        Ordering::Equal == self.cmp(other)
+    }
+}
+
+impl Eq for Solution {}
+
+impl PartialOrd for Solution {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Solution {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let rank = |x: &Solution| match x {
+            Unsolved => 0,
+            Str(_) => 2,
+            Lines(_) => 3,
+            List(_) => 4,
+            _ => 1,
+        };
+
+        match (self, other) {
+            (Str(a), Str(b)) => a.cmp(b),
+            (Lines(a), Lines(b)) => a.cmp(b),
+            (List(a), List(b)) => a.cmp(b),
+            _ if rank(self) != rank(other) => rank(self).cmp(&rank(other)),
+            _ => self.to_bigint().cmp(&other.to_bigint()),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($from:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$from> for Solution {
+                fn from(x: $from) -> Self {
+                    $variant(x.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from! {
+    i8 => I32,
+    i16 => I32,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U32,
+    u16 => U32,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    num::BigInt => BigInt,
+    String => Str,
+    &str => Str,
+}
+
+impl From<isize> for Solution {
+    fn from(x: isize) -> Self {
+        I64(x as i64)
+    }
+}
+
+impl From<usize> for Solution {
+    fn from(x: usize) -> Self {
+        U64(x as u64)
+    }
+}
+
+impl<T: Into<Solution>> From<Vec<T>> for Solution {
+    fn from(x: Vec<T>) -> Self {
+        List(x.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<A: Into<Solution>, B: Into<Solution>> From<(A, B)> for Solution {
+    fn from((a, b): (A, B)) -> Self {
+        List(vec![a.into(), b.into()])
+    }
+}
+
+impl<A: Into<Solution>, B: Into<Solution>, C: Into<Solution>> From<(A, B, C)> for Solution {
+    fn from((a, b, c): (A, B, C)) -> Self {
+        List(vec![a.into(), b.into(), c.into()])
+    }
 }
 
 impl Display for Solution {
@@ -21,7 +161,56 @@ impl Display for Solution {
             U32(x) => x.fmt(f),
             U64(x) => x.fmt(f),
             U128(x) => x.fmt(f),
+            BigInt(x) => x.fmt(f),
             Str(x) => x.fmt(f),
+            Lines(x) => x.join("\n").fmt(f),
+            Unsolved => "not yet solved".fmt(f),
+            List(x) => {
+                for (index, x) in x.iter().enumerate() {
+                    if 0 != index {
+                        f.write_str(",")?;
+                    }
+
+                    x.fmt(f)?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Solution;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(Solution::U32(13), Solution::U64(13));
+        assert_eq!(Solution::from(-4i8), Solution::I128(-4));
+        assert_eq!(Solution::from(7usize), Solution::from(num::BigInt::from(7)));
+        assert_ne!(Solution::U32(13), Solution::from("13"));
+
+        assert!(Solution::I64(-1) < Solution::U128(u128::MAX));
+        assert!(Solution::U128(u128::MAX) < Solution::from(num::BigInt::from(u128::MAX) + 1));
+        assert!(Solution::U32(u32::MAX) < Solution::from(""));
+
+        let big = num::BigInt::from(u128::MAX) * 1000;
+
+        assert_eq!(
+            Solution::from(big).to_string(),
+            "340282366920938463463374607431768211455000"
+        );
+        assert_eq!(Solution::from(3u8).kind(), "U32");
+
+        assert_eq!(Solution::from((90u32, -3i64)).to_string(), "90,-3");
+        assert_eq!(Solution::from(vec!["a", "b"]), Solution::from(("a", "b")));
+        assert!(Solution::from(vec![1u8]) > Solution::from("z"));
+        assert!(Solution::Unsolved < Solution::I64(i64::MIN));
+        assert_ne!(Solution::Unsolved, Solution::U64(0));
+
+        let grid = crate::etc::Grid::parse("#.\n.#", |c| Some('#' == c)).unwrap();
+
+        assert_eq!(Solution::picture(&grid).to_string(), "#.\n.#");
+    }
+}
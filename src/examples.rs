@@ -0,0 +1,79 @@
+use crate::answers::Answers;
+use crate::input;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+///////////////////////////////////////////////////////////////////////////////
+
+pub const DEFAULT_DIR: &str = "examples";
+
+/// An example input from a puzzle's text, stored as `examples/dayXX-N`, along with the answers it
+/// should give, stored in the same format as the accepted answers in `examples/dayXX-N.answers`.
+pub struct Example {
+    /// Counts up from 1 for each day.
+    pub number: usize,
+    pub input: String,
+    pub answers: Answers,
+}
+
+fn path_for(dir: &Path, day: u8, number: usize) -> PathBuf {
+    dir.join(format!("day{:02}-{}", day, number))
+}
+
+/// Every example for `day` in `dir`, stopping at the first number without an input file.
+pub fn load(dir: &Path, day: u8) -> Result<Vec<Example>, String> {
+    let mut examples = Vec::new();
+
+    for number in 1.. {
+        let path = path_for(dir, day, number);
+
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input::normalise(&input),
+            Err(e) if io::ErrorKind::NotFound == e.kind() => break,
+            Err(e) => {
+                return Err(format!(
+                    "could not read example '{}': {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+
+        let answers = Answers::read(&path.with_extension("answers"))?;
+
+        examples.push(Example {
+            number,
+            input,
+            answers,
+        });
+    }
+
+    if examples.is_empty() {
+        return Err(format!(
+            "no examples found (expected '{}')",
+            path_for(dir, day, 1).display()
+        ));
+    }
+
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn it_works() {
+        let examples = super::load(Path::new(super::DEFAULT_DIR), 8).unwrap();
+
+        assert_eq!(
+            examples.iter().map(|x| x.number).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(examples[2].answers.part1, None);
+        assert_eq!(examples[2].answers.part2.as_deref(), Some("6"));
+
+        assert!(super::load(Path::new(super::DEFAULT_DIR), 25).is_err());
+    }
+}
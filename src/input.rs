@@ -0,0 +1,103 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Where the runner should look for each day's puzzle input.
+pub enum InputSource {
+    /// A directory holding one `dayXX` file per day.
+    Directory(PathBuf),
+    /// A single file used as the input for the (one) requested day.
+    File(PathBuf),
+    /// Standard input, given as `--input -`, used for the (one) requested day.
+    Stdin,
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Directory(PathBuf::from("input"))
+    }
+}
+
+impl InputSource {
+    pub fn path_for(&self, day: u8) -> PathBuf {
+        match self {
+            Self::Directory(dir) => dir.join(format!("day{:02}", day)),
+            Self::File(file) => file.clone(),
+            Self::Stdin => PathBuf::from("-"),
+        }
+    }
+
+    /// The input for `day`, normalised so that parsers never see `\r`s, trailing whitespace or a
+    /// byte order mark.
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        let path = self.path_for(day);
+
+        let text = match self {
+            Self::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            }
+            _ => fs::read_to_string(&path),
+        };
+
+        text.map(|x| normalise(&x))
+            .map_err(|error| InputError { path, error })
+    }
+}
+
+/// Strips a leading byte order mark, any `\r`s at the end of lines, whitespace at the end of
+/// lines, and blank lines at the end. Non-empty text always ends with a single newline.
+pub fn normalise(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut normalised = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        normalised.push_str(line.trim_end());
+        normalised.push('\n');
+    }
+
+    let len = normalised.trim_end().len();
+
+    normalised.truncate(len);
+
+    if !normalised.is_empty() {
+        normalised.push('\n');
+    }
+
+    normalised
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    path: PathBuf,
+    error: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read input '{}': {}",
+            self.path.display(),
+            self.error
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        assert_eq!(
+            super::normalise("\u{feff}ab \r\n\r\ncd\t\r\n\r\n  \n"),
+            "ab\n\ncd\n"
+        );
+        assert_eq!(super::normalise("ab"), "ab\n");
+        assert_eq!(super::normalise("\r\n"), "");
+        assert_eq!(super::normalise("  ab"), "  ab\n");
+    }
+}
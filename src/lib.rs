@@ -0,0 +1,11 @@
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod etc;
+pub mod examples;
+pub mod input;
+pub mod pool;
+pub mod report;
+pub mod runner;
+
+pub use etc::{Day, ParseError, Parsed, Solution};
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Runs `func` over every item on up to `jobs` threads, calling `done` with each result in the
+/// original order of the items as soon as it (and everything before it) has finished.
+pub fn map_in_order<T, R>(
+    items: &[T],
+    jobs: usize,
+    func: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(&T, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 {
+        for item in items {
+            done(item, func(item));
+        }
+
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, func) = (&next, &func);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                if sender.send((index, func(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Only the workers hold senders now, so the receiver finishes once they all have.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut reported = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&reported) {
+                done(&items[reported], result);
+                reported += 1;
+            }
+        }
+    });
+}
+
+/// Runs `a` and `b`, on two threads if `parallel` is set. The second thread shares the name of
+/// the current thread, and a panic on it is passed on as is.
+pub fn join<A, B>(
+    parallel: bool,
+    a: impl FnOnce() -> A + Send,
+    b: impl FnOnce() -> B + Send,
+) -> (A, B)
+where
+    A: Send,
+    B: Send,
+{
+    if !parallel {
+        return (a(), b());
+    }
+
+    let mut builder = thread::Builder::new();
+
+    if let Some(name) = thread::current().name() {
+        builder = builder.name(name.to_string());
+    }
+
+    thread::scope(|scope| {
+        let b = builder.spawn_scoped(scope, b).unwrap();
+        let a = a();
+
+        (a, b.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        let items: Vec<u64> = (0..50).collect();
+
+        let mut results = Vec::new();
+
+        super::map_in_order(&items, 4, |x| x * x, |x, r| results.push((*x, r)));
+
+        assert_eq!(
+            results,
+            items.iter().map(|x| (*x, x * x)).collect::<Vec<_>>()
+        );
+
+        assert_eq!(super::join(true, || 1, || "two"), (1, "two"));
+    }
+}
@@ -0,0 +1,576 @@
+use crate::answers::Check;
+use crate::bench::{Bench, Stats};
+use crate::days;
+use crate::examples;
+use crate::runner::{as_ms, Run};
+use crate::{Day, Solution};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            x => Err(format!(
+                "Not a valid format: {} (expected text, json or csv)",
+                x
+            )),
+        }
+    }
+}
+
+pub enum Outcome {
+    Solved {
+        run: Run,
+        bench: Option<Box<Bench>>,
+        /// Only filled in for the parts that ran when verifying against the accepted answers.
+        checks: (Option<Check>, Option<Check>),
+    },
+    Failed(String),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        match self {
+            Outcome::Solved {
+                checks: (c1, c2), ..
+            } => [c1, c2]
+                .iter()
+                .any(|x| matches!(x, Some(Check::Fail { .. }))),
+            Outcome::Failed(_) => true,
+        }
+    }
+}
+
+pub struct DayReport {
+    pub number: u8,
+    pub title: &'static str,
+    /// Which of the day's examples was run instead of its input, if any.
+    pub example: Option<usize>,
+    pub outcome: Outcome,
+}
+
+impl DayReport {
+    /// The title, plus which example this is if it is one.
+    fn heading(&self) -> String {
+        match self.example {
+            Some(example) => format!("{} (example {})", self.title, example),
+            None => self.title.to_string(),
+        }
+    }
+
+    /// The time the day took to run, which is the median when benchmarking. Parts that are not
+    /// solved yet don't count, and neither does parsing for a day with nothing solved.
+    fn elapsed(&self) -> Duration {
+        let (run, bench, checks) = match &self.outcome {
+            Outcome::Solved { run, bench, checks } => (run, bench, checks),
+            Outcome::Failed(_) => return Duration::ZERO,
+        };
+
+        let parts = parts(run, bench, checks);
+
+        if self.unsolved_parts().len() == parts.iter().flatten().count() {
+            return Duration::ZERO;
+        }
+
+        let total = bench
+            .as_ref()
+            .map_or(run.timings.total(), |x| x.total.median);
+
+        let unsolved: Duration = parts
+            .iter()
+            .flatten()
+            .filter(|x| x.solution.is_unsolved())
+            .map(|x| x.elapsed)
+            .sum();
+
+        total.saturating_sub(unsolved)
+    }
+
+    /// The numbers of the parts that ran but are not solved yet.
+    fn unsolved_parts(&self) -> Vec<u8> {
+        match &self.outcome {
+            Outcome::Solved { run, .. } => [&run.solutions.0, &run.solutions.1]
+                .iter()
+                .zip(1..)
+                .filter(|(x, _)| x.as_ref().is_some_and(Solution::is_unsolved))
+                .map(|(_, number)| number)
+                .collect(),
+            Outcome::Failed(_) => Vec::new(),
+        }
+    }
+}
+
+/// Everything the report needs to know about one part that ran.
+struct PartReport<'a> {
+    number: u8,
+    solution: &'a Solution,
+    /// The median when benchmarking.
+    elapsed: Duration,
+    bench: Option<&'a Stats>,
+    check: Option<&'a Check>,
+}
+
+fn parts<'a>(
+    run: &'a Run,
+    bench: &'a Option<Box<Bench>>,
+    checks: &'a (Option<Check>, Option<Check>),
+) -> [Option<PartReport<'a>>; 2] {
+    let part = |number, solution: &'a Option<Solution>, elapsed: Option<Duration>, stats, check| {
+        let solution = solution.as_ref()?;
+        let bench: Option<&Stats> = stats;
+
+        Some(PartReport {
+            number,
+            solution,
+            elapsed: bench.map_or(elapsed.unwrap_or_default(), |x| x.median),
+            bench,
+            check,
+        })
+    };
+
+    [
+        part(
+            1,
+            &run.solutions.0,
+            run.timings.part1,
+            bench.as_ref().and_then(|x| x.part1.as_ref()),
+            checks.0.as_ref(),
+        ),
+        part(
+            2,
+            &run.solutions.1,
+            run.timings.part2,
+            bench.as_ref().and_then(|x| x.part2.as_ref()),
+            checks.1.as_ref(),
+        ),
+    ]
+}
+
+/// The parse time, which is the median when benchmarking.
+fn parse_time(run: &Run, bench: &Option<Box<Bench>>) -> Option<Duration> {
+    match bench {
+        Some(bench) => bench.parse.as_ref().map(|x| x.median),
+        None => run.timings.parse,
+    }
+}
+
+/// Writes out each day as it completes in the chosen format.
+pub struct Reporter {
+    format: Format,
+    benchmarking: bool,
+    days: usize,
+    runtime: Duration,
+    // Pass, fail and missing counts when verifying.
+    verified: Option<[usize; 3]>,
+    // The days with parts that are not solved yet, and which parts.
+    unsolved: Vec<(u8, Vec<u8>)>,
+}
+
+impl Reporter {
+    pub fn new(format: Format, benchmarking: bool) -> Self {
+        match format {
+            Format::Text => (),
+            Format::Json => println!("{{\n  \"days\": ["),
+            Format::Csv => println!(
+                "day,title,part1_type,part1_value,part1_ms,part2_type,part2_value,part2_ms,parse_ms,total_ms,error,part1_check,part2_check"
+            ),
+        }
+
+        Reporter {
+            format,
+            benchmarking,
+            days: 0,
+            runtime: Duration::ZERO,
+            verified: None,
+            unsolved: Vec::new(),
+        }
+    }
+
+    pub fn day(&mut self, report: &DayReport) {
+        match self.format {
+            Format::Text => Self::text(report),
+            Format::Json => self.json(report),
+            Format::Csv => Self::csv(report),
+        }
+
+        if let Outcome::Solved {
+            checks: (c1, c2), ..
+        } = &report.outcome
+        {
+            for check in [c1, c2].into_iter().flatten() {
+                let verified = self.verified.get_or_insert([0; 3]);
+
+                match check {
+                    Check::Pass => verified[0] += 1,
+                    Check::Fail { .. } => verified[1] += 1,
+                    Check::Missing => verified[2] += 1,
+                }
+            }
+        }
+
+        let unsolved = report.unsolved_parts();
+
+        if !unsolved.is_empty() {
+            self.unsolved.push((report.number, unsolved));
+        }
+
+        self.days += 1;
+        self.runtime += report.elapsed();
+    }
+
+    /// `wall_clock` is how long the whole run took, which is less than the summed runtime of
+    /// the days when they ran in parallel.
+    pub fn finish(self, wall_clock: Duration) {
+        let runtime = as_ms(self.runtime);
+        let wall_clock = as_ms(wall_clock);
+
+        match self.format {
+            Format::Text => {
+                if let Some([pass, fail, missing]) = self.verified {
+                    println!(
+                        "Verified: {} passed, {} failed, {} missing",
+                        pass, fail, missing
+                    );
+                }
+
+                if !self.unsolved.is_empty() {
+                    let days: Vec<_> = self
+                        .unsolved
+                        .iter()
+                        .map(|(day, parts)| match parts[..] {
+                            [part] => format!("{:02} (part {})", day, part),
+                            _ => format!("{:02}", day),
+                        })
+                        .collect();
+
+                    println!("🚧 Not yet solved: {}", days.join(", "));
+                }
+
+                if self.benchmarking {
+                    println!("Total runtime (sum of medians): {:.4} ms", runtime);
+                } else {
+                    println!("Total runtime: {:.4} ms", runtime);
+                }
+
+                println!("Wall clock: {:.4} ms", wall_clock);
+            }
+            Format::Json => {
+                if 0 != self.days {
+                    println!();
+                }
+
+                let unsolved: Vec<_> = self.unsolved.iter().map(|(x, _)| x.to_string()).collect();
+
+                println!(
+                    "  ],\n  \"unsolved\": [{}],\n  \"total_ms\": {:.4},\n  \"wall_ms\": {:.4}\n}}",
+                    unsolved.join(", "),
+                    runtime,
+                    wall_clock
+                );
+            }
+            Format::Csv => {
+                println!("total,,,,,,,,,{:.4},,,", runtime);
+                println!("wall,,,,,,,,,{:.4},,,", wall_clock);
+            }
+        }
+    }
+
+    fn text(report: &DayReport) {
+        println!("🎄 Day {:02}: {}", report.number, report.heading());
+
+        let (run, bench, checks) = match &report.outcome {
+            Outcome::Solved { run, bench, checks } => (run, bench, checks),
+            Outcome::Failed(error) => {
+                eprintln!("  ❌ {}", error);
+                return;
+            }
+        };
+
+        let parts = parts(run, bench, checks);
+
+        for part in parts.iter().flatten() {
+            let solution = part.solution.to_string();
+            let check = part.check.map_or(String::new(), text_check);
+
+            // Multi-line answers (like block letters) go underneath so their rows line up.
+            if solution.contains('\n') {
+                println!("  🎁 Part {}:{}", part.number, check);
+
+                for line in solution.lines() {
+                    println!("      {}", line);
+                }
+            } else {
+                println!("  🎁 Part {}: {}{}", part.number, solution, check);
+            }
+        }
+
+        // There's nothing worth timing until something is solved.
+        if parts.iter().flatten().all(|x| x.solution.is_unsolved()) {
+            return;
+        }
+
+        if let Some(bench) = bench {
+            println!("  📊 Benchmarked over {} runs", bench.runs);
+
+            if let Some(parse) = &bench.parse {
+                println!("  ⏱️  Parse: {}", parse);
+            }
+
+            for part in parts.iter().flatten().filter(|x| !x.solution.is_unsolved()) {
+                if let Some(stats) = part.bench {
+                    println!("  ⏱️  Part {}: {}", part.number, stats);
+                }
+            }
+
+            println!("  ⏱️  Elapsed: {}", bench.total);
+        } else {
+            if let Some(parse) = run.timings.parse {
+                println!("  ⏱️  Parse: {:.4} ms", as_ms(parse));
+            }
+
+            for part in parts.iter().flatten().filter(|x| !x.solution.is_unsolved()) {
+                println!("  ⏱️  Part {}: {:.4} ms", part.number, as_ms(part.elapsed));
+            }
+
+            println!("  ⏱️  Elapsed: {:.4} ms", as_ms(report.elapsed()));
+        }
+    }
+
+    fn json(&self, report: &DayReport) {
+        if 0 != self.days {
+            println!(",");
+        }
+
+        let mut fields = vec![
+            format!("\"day\": {}", report.number),
+            format!("\"title\": {}", json_string(report.title)),
+            format!(
+                "\"example\": {}",
+                report.example.map_or("null".to_string(), |x| x.to_string())
+            ),
+        ];
+
+        match &report.outcome {
+            Outcome::Solved { run, bench, checks } => {
+                for (index, part) in parts(run, bench, checks).iter().enumerate() {
+                    fields.push(format!(
+                        "\"part{}\": {}",
+                        index + 1,
+                        part.as_ref().map_or("null".to_string(), json_part)
+                    ));
+                }
+
+                fields.push(format!(
+                    "\"parse_ms\": {}",
+                    parse_time(run, bench)
+                        .map_or("null".to_string(), |x| format!("{:.4}", as_ms(x)))
+                ));
+                fields.push(format!("\"total_ms\": {:.4}", as_ms(report.elapsed())));
+                fields.push(format!(
+                    "\"bench\": {}",
+                    bench.as_ref().map_or("null".to_string(), |x| json_bench(x))
+                ));
+                fields.push("\"error\": null".to_string());
+            }
+            Outcome::Failed(error) => {
+                fields.push("\"part1\": null".to_string());
+                fields.push("\"part2\": null".to_string());
+                fields.push("\"parse_ms\": null".to_string());
+                fields.push("\"total_ms\": null".to_string());
+                fields.push("\"bench\": null".to_string());
+                fields.push(format!("\"error\": {}", json_string(error)));
+            }
+        }
+
+        print!("    {{ {} }}", fields.join(", "));
+    }
+
+    fn csv(report: &DayReport) {
+        let mut fields = vec![report.number.to_string(), csv_field(&report.heading())];
+
+        match &report.outcome {
+            Outcome::Solved { run, bench, checks } => {
+                let parts = parts(run, bench, checks);
+
+                for part in &parts {
+                    match part {
+                        Some(part) => {
+                            fields.push(part.solution.kind().to_string());
+                            fields.push(if part.solution.is_unsolved() {
+                                String::new()
+                            } else {
+                                csv_field(&part.solution.to_string())
+                            });
+                            fields.push(format!("{:.4}", as_ms(part.elapsed)));
+                        }
+                        None => fields.resize(fields.len() + 3, String::new()),
+                    }
+                }
+
+                fields.push(
+                    parse_time(run, bench).map_or(String::new(), |x| format!("{:.4}", as_ms(x))),
+                );
+                fields.push(format!("{:.4}", as_ms(report.elapsed())));
+                fields.push(String::new());
+
+                for part in &parts {
+                    fields.push(
+                        part.as_ref()
+                            .and_then(|x| x.check)
+                            .map_or(String::new(), |x| x.as_str().to_string()),
+                    );
+                }
+            }
+            Outcome::Failed(error) => {
+                fields.resize(fields.len() + 8, String::new());
+                fields.push(csv_field(error));
+                fields.resize(fields.len() + 2, String::new());
+            }
+        }
+
+        println!("{}", fields.join(","));
+    }
+}
+
+fn text_check(check: &Check) -> String {
+    match check {
+        Check::Pass => " ✅".to_string(),
+        Check::Fail { expected } => format!(" ❌ (expected {})", expected),
+        Check::Missing => " ❔ (no accepted answer)".to_string(),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+
+    result.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+
+    result
+}
+
+fn json_stats(stats: &Stats) -> String {
+    format!(
+        "{{ \"min_ms\": {:.4}, \"median_ms\": {:.4}, \"mean_ms\": {:.4}, \"stddev_ms\": {:.4}, \"p95_ms\": {:.4} }}",
+        as_ms(stats.min),
+        as_ms(stats.median),
+        as_ms(stats.mean),
+        as_ms(stats.stddev),
+        as_ms(stats.p95)
+    )
+}
+
+fn json_bench(bench: &Bench) -> String {
+    format!(
+        "{{ \"runs\": {}, \"parse\": {}, \"part1\": {}, \"part2\": {}, \"total\": {} }}",
+        bench.runs,
+        bench.parse.as_ref().map_or("null".to_string(), json_stats),
+        bench.part1.as_ref().map_or("null".to_string(), json_stats),
+        bench.part2.as_ref().map_or("null".to_string(), json_stats),
+        json_stats(&bench.total)
+    )
+}
+
+// Values are always written as strings so that 128-bit answers survive JSON parsers that only
+// have doubles.
+fn json_part(part: &PartReport) -> String {
+    let expected = match part.check {
+        Some(Check::Fail { expected }) => json_string(expected),
+        _ => "null".to_string(),
+    };
+
+    format!(
+        "{{ \"type\": \"{}\", \"value\": {}, \"ms\": {:.4}, \"check\": {}, \"expected\": {} }}",
+        part.solution.kind(),
+        if part.solution.is_unsolved() {
+            "null".to_string()
+        } else {
+            json_string(&part.solution.to_string())
+        },
+        as_ms(part.elapsed),
+        part.check
+            .map_or("null".to_string(), |x| format!("\"{}\"", x.as_str())),
+        expected
+    )
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Prints what `--check-input` found wrong with a day's input.
+pub fn problems(day: &dyn Day, problems: &[String]) {
+    println!("🎄 Day {:02}: {}", day.number(), day.title());
+
+    if !problems.is_empty() {
+        for problem in problems {
+            println!("  ❌ {}", problem);
+        }
+    } else if day.is_implemented() {
+        println!("  ✅ No problems found");
+    } else {
+        println!("  🚧 Not yet solved, so there is nothing to check");
+    }
+}
+
+/// Prints every registered day and whether it has been implemented yet.
+pub fn list_days() {
+    for day in days::ALL {
+        let status = if day.is_implemented() { "✅" } else { "🚧" };
+
+        let examples =
+            examples::load(Path::new(examples::DEFAULT_DIR), day.number()).map_or(0, |x| x.len());
+
+        println!(
+            "{} Day {:02}: {} ({} example{})",
+            status,
+            day.number(),
+            day.title(),
+            examples,
+            if 1 == examples { "" } else { "s" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        assert_eq!(super::json_string("a \"b\"\n"), r#""a \"b\"\n""#);
+        assert_eq!(super::csv_field("Trebuchet?!"), "Trebuchet?!");
+        assert_eq!(super::csv_field("a, \"b\""), r#""a, ""b""""#);
+    }
+}
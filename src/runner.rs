@@ -0,0 +1,513 @@
+use crate::answers::Answers;
+use crate::bench;
+use crate::days;
+use crate::etc::{log, params};
+use crate::examples::{self, Example};
+use crate::input::InputSource;
+use crate::pool;
+use crate::report::{self, DayReport, Format, Outcome, Reporter};
+use crate::{Day, ParseError, Solution};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Which parts of a day to run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parts {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+impl Parts {
+    pub fn part1(self) -> bool {
+        matches!(self, Parts::Both | Parts::Part1)
+    }
+
+    pub fn part2(self) -> bool {
+        matches!(self, Parts::Both | Parts::Part2)
+    }
+}
+
+/// Each part is `None` when it was not asked to run.
+pub struct Timings {
+    /// Only recorded for days that parse their input separately from solving it.
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        [self.parse, self.part1, self.part2].iter().flatten().sum()
+    }
+}
+
+pub struct Run {
+    pub solutions: (Option<Solution>, Option<Solution>),
+    pub timings: Timings,
+}
+
+fn time<T>(func: impl FnOnce() -> T) -> (T, Duration) {
+    let time = Instant::now();
+    let result = func();
+    (result, time.elapsed())
+}
+
+type Timed = (Option<Solution>, Option<Duration>);
+
+fn time_if(
+    run: bool,
+    func: impl FnOnce() -> Result<Solution, ParseError>,
+) -> Result<Timed, ParseError> {
+    if run {
+        let (solution, elapsed) = time(func);
+        Ok((Some(solution?), Some(elapsed)))
+    } else {
+        Ok((None, None))
+    }
+}
+
+/// Runs the requested parts of `day`, running the two parts at the same time when
+/// `parallel_parts` is set. Errors in the input are tagged with the day they came from.
+pub fn run(
+    day: &dyn Day,
+    input: &str,
+    parts: Parts,
+    parallel_parts: bool,
+) -> Result<Run, ParseError> {
+    run_untagged(day, input, parts, parallel_parts).map_err(|e| e.in_day(day.number()))
+}
+
+fn run_untagged(
+    day: &dyn Day,
+    input: &str,
+    parts: Parts,
+    parallel_parts: bool,
+) -> Result<Run, ParseError> {
+    let parallel_parts = parallel_parts && Parts::Both == parts;
+
+    let (parsed, parse) = time(|| day.parse(input));
+
+    let (part1, part2, parse) = if let Some(parsed) = &parsed? {
+        let (part1, part2) = pool::join(
+            parallel_parts,
+            || time_if(parts.part1(), || parsed.part1()),
+            || time_if(parts.part2(), || parsed.part2()),
+        );
+
+        (part1, part2, Some(parse))
+    } else {
+        let (part1, part2) = pool::join(
+            parallel_parts,
+            || time_if(parts.part1(), || day.part1(input)),
+            || time_if(parts.part2(), || day.part2(input)),
+        );
+
+        (part1, part2, None)
+    };
+
+    let ((p1, part1), (p2, part2)) = (part1?, part2?);
+
+    Ok(Run {
+        solutions: (p1, p2),
+        timings: Timings {
+            parse,
+            part1,
+            part2,
+        },
+    })
+}
+
+/// Why a day failed to produce a run.
+pub enum Failure {
+    BadInput(ParseError),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::BadInput(error) => write!(f, "bad input: {}", error),
+            Failure::Panicked(message) => write!(f, "panicked: {}", message),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {}s", timeout.as_secs_f64()),
+        }
+    }
+}
+
+const THREAD_PREFIX: &str = "day";
+
+/// Stops the default panic hook from printing panics on the threads `run_isolated` uses, since
+/// those panics are reported as part of the day instead.
+pub fn silence_day_panics() {
+    let default = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let is_day = thread::current()
+            .name()
+            .is_some_and(|x| x.starts_with(THREAD_PREFIX));
+
+        if !is_day {
+            default(info);
+        }
+    }));
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<unknown panic payload>".to_string()
+    }
+}
+
+/// Like `run`, but on its own thread so that a panic or a run longer than `timeout` is returned
+/// as a failure rather than taking down the runner. A day that times out is left running in the
+/// background, since there is no way to stop a thread.
+pub fn run_isolated(
+    day: &'static dyn Day,
+    input: Arc<str>,
+    parts: Parts,
+    parallel_parts: bool,
+    timeout: Option<Duration>,
+) -> Result<Run, Failure> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .name(format!("{}{:02}", THREAD_PREFIX, day.number()))
+        .spawn(move || {
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| run(day, &input, parts, parallel_parts)));
+
+            // If we timed out nobody is listening anymore, and that is fine.
+            let _ = sender.send(result);
+        })
+        .expect("failed to spawn a thread to run the day on");
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    match result {
+        Ok(Ok(result)) => result.map_err(Failure::BadInput),
+        Ok(Err(payload)) => Err(Failure::Panicked(panic_message(payload))),
+        Err(RecvTimeoutError::Timeout) => Err(Failure::TimedOut(timeout.unwrap())),
+        Err(RecvTimeoutError::Disconnected) => Err(Failure::Panicked(
+            "the thread exited without a result".to_string(),
+        )),
+    }
+}
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Everything about how to run a set of days.
+pub struct Options {
+    pub days: Vec<u8>,
+    pub source: InputSource,
+    pub parts: Parts,
+    pub format: Format,
+    pub jobs: usize,
+    pub parallel_parts: bool,
+    pub timeout: Option<Duration>,
+    pub bench: Option<usize>,
+    /// The directory of accepted answers to verify against, if any.
+    pub answers: Option<PathBuf>,
+    /// Run each day against the examples in this directory instead of its input.
+    pub examples: Option<PathBuf>,
+    /// Let days log what they are doing to stderr with `verbose!`.
+    pub verbose: bool,
+    /// Settings for days to look up with `params::get`, like `day02.bag`.
+    pub params: Vec<(String, String)>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            days: Vec::new(),
+            source: InputSource::default(),
+            parts: Parts::default(),
+            format: Format::default(),
+            jobs: 1,
+            parallel_parts: false,
+            timeout: Some(DEFAULT_TIMEOUT),
+            bench: None,
+            answers: None,
+            examples: None,
+            verbose: false,
+            params: Vec::new(),
+        }
+    }
+}
+
+/// What a stub day reports for the parts asked for, without needing an input to run on.
+fn unsolved(parts: Parts) -> Outcome {
+    let run = Run {
+        solutions: (
+            parts.part1().then_some(Solution::Unsolved),
+            parts.part2().then_some(Solution::Unsolved),
+        ),
+        timings: Timings {
+            parse: None,
+            part1: None,
+            part2: None,
+        },
+    };
+
+    Outcome::Solved {
+        run,
+        bench: None,
+        checks: (None, None),
+    }
+}
+
+/// Loads the input (and any accepted answers) for `solver`, runs it in isolation, and benchmarks
+/// it if asked to. Stub days are reported as unsolved without loading anything.
+pub fn run_day(solver: &'static dyn Day, options: &Options) -> Outcome {
+    if !solver.is_implemented() {
+        return unsolved(options.parts);
+    }
+
+    let day = solver.number();
+
+    let input: Arc<str> = match options.source.load(day) {
+        Ok(input) => input.into(),
+        Err(error) => return Outcome::Failed(error.to_string()),
+    };
+
+    let answers = match options
+        .answers
+        .as_deref()
+        .map(|dir| Answers::load(dir, day))
+        .transpose()
+    {
+        Ok(answers) => answers,
+        Err(error) => return Outcome::Failed(error),
+    };
+
+    run_input(solver, input, answers.as_ref(), options.parts, options)
+}
+
+/// Like `run_day`, but on one of the day's examples and always checked against its answers. An
+/// example with an answer for only one part only runs that part.
+pub fn run_example(solver: &'static dyn Day, example: &Example, options: &Options) -> Outcome {
+    let answers = &example.answers;
+
+    let parts = match (answers.part1.is_some(), answers.part2.is_some()) {
+        (true, false) if options.parts.part1() => Parts::Part1,
+        (false, true) if options.parts.part2() => Parts::Part2,
+        _ => options.parts,
+    };
+
+    run_input(
+        solver,
+        example.input.as_str().into(),
+        Some(answers),
+        parts,
+        options,
+    )
+}
+
+fn run_input(
+    solver: &'static dyn Day,
+    input: Arc<str>,
+    answers: Option<&Answers>,
+    parts: Parts,
+    options: &Options,
+) -> Outcome {
+    if let Err(error) = solver.check_params() {
+        return Outcome::Failed(error);
+    }
+
+    let run = match run_isolated(
+        solver,
+        input.clone(),
+        parts,
+        options.parallel_parts,
+        options.timeout,
+    ) {
+        Ok(run) => run,
+        Err(failure) => return Outcome::Failed(failure.to_string()),
+    };
+
+    let checks = answers.map_or((None, None), |x| x.check(&run.solutions));
+
+    let bench = match options
+        .bench
+        .map(|runs| bench::bench(solver, &input, parts, runs))
+        .transpose()
+    {
+        Ok(bench) => bench.map(Box::new),
+        Err(error) => return Outcome::Failed(error.to_string()),
+    };
+
+    Outcome::Solved { bench, run, checks }
+}
+
+/// One report's worth of work.
+enum Job {
+    Input(&'static dyn Day),
+    Example(&'static dyn Day, Example),
+    /// The day's examples could not be loaded, for this reason.
+    NoExamples(&'static dyn Day, String),
+}
+
+impl Job {
+    fn solver(&self) -> &'static dyn Day {
+        match self {
+            Job::Input(solver) | Job::Example(solver, _) | Job::NoExamples(solver, _) => *solver,
+        }
+    }
+}
+
+/// Sets up the logging and parameters that days read from anywhere.
+fn set_globals(options: &Options) {
+    log::set_verbose(options.verbose);
+    params::set(options.params.iter().cloned());
+}
+
+/// The jobs for every registered day in `options`.
+fn jobs(options: &Options) -> Vec<Job> {
+    let mut jobs = Vec::new();
+
+    for solver in options.days.iter().filter_map(|x| days::get(*x)) {
+        match options.examples.as_deref() {
+            // Stub days have no examples either, and are unsolved whatever they run on.
+            _ if !solver.is_implemented() => jobs.push(Job::Input(solver)),
+            None => jobs.push(Job::Input(solver)),
+            Some(dir) => match examples::load(dir, solver.number()) {
+                Ok(examples) => jobs.extend(examples.into_iter().map(|x| Job::Example(solver, x))),
+                Err(error) => jobs.push(Job::NoExamples(solver, error)),
+            },
+        }
+    }
+
+    jobs
+}
+
+/// Runs every day in `options`, reporting each one in order as it finishes. Days that are not
+/// registered are skipped. Returns whether all of them succeeded.
+pub fn run_days(options: &Options) -> bool {
+    silence_day_panics();
+    set_globals(options);
+
+    let mut reporter = Reporter::new(options.format, options.bench.is_some());
+    let mut failed = false;
+
+    let wall_clock = Instant::now();
+
+    pool::map_in_order(
+        &jobs(options),
+        options.jobs,
+        |job| match job {
+            Job::Input(solver) => run_day(*solver, options),
+            Job::Example(solver, example) => run_example(*solver, example, options),
+            Job::NoExamples(_, error) => Outcome::Failed(error.clone()),
+        },
+        |job, outcome| {
+            failed |= outcome.is_failure();
+
+            reporter.day(&DayReport {
+                number: job.solver().number(),
+                title: job.solver().title(),
+                example: match job {
+                    Job::Example(_, example) => Some(example.number),
+                    _ => None,
+                },
+                outcome,
+            });
+        },
+    );
+
+    reporter.finish(wall_clock.elapsed());
+
+    !failed
+}
+
+/// Validates the input of every day in `options` instead of solving it, reporting every problem
+/// found. Returns whether all of the inputs were fine.
+pub fn check_inputs(options: &Options) -> bool {
+    set_globals(options);
+
+    let mut fine = true;
+
+    for solver in options.days.iter().filter_map(|x| days::get(*x)) {
+        // Stub days have no input to check, so don't read any (which could use up stdin).
+        if !solver.is_implemented() {
+            report::problems(solver, &[]);
+            continue;
+        }
+
+        let problems: Vec<_> = match options.source.load(solver.number()) {
+            Ok(input) => solver
+                .validate(&input)
+                .into_iter()
+                .map(|x| x.to_string())
+                .collect(),
+            Err(error) => vec![error.to_string()],
+        };
+
+        fine &= problems.is_empty();
+
+        report::problems(solver, &problems);
+    }
+
+    fine
+}
+
+pub fn as_ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, Parts};
+    use crate::input::InputSource;
+    use crate::report::Outcome;
+    use crate::*;
+
+    #[test]
+    fn it_works() {
+        // Day 12 is a stub and there is no input for it, which is fine.
+        let options = Options {
+            days: vec![12],
+            source: InputSource::Directory("no-such-directory".into()),
+            parts: Parts::Part2,
+            ..Options::default()
+        };
+
+        let Outcome::Solved { run, .. } = super::run_day(days::get(12).unwrap(), &options) else {
+            panic!("expected day 12 to be reported as unsolved");
+        };
+
+        assert!(run.solutions.0.is_none());
+        assert!(run.solutions.1.is_some_and(|x| x.is_unsolved()));
+
+        let options = Options {
+            examples: Some("no-such-directory".into()),
+            ..options
+        };
+
+        assert!(matches!(super::jobs(&options)[..], [super::Job::Input(_)]));
+        assert!(super::check_inputs(&options));
+
+        // Even stdin is left alone.
+        let options = Options {
+            source: InputSource::Stdin,
+            ..options
+        };
+
+        assert!(super::check_inputs(&options));
+    }
+}